@@ -1,5 +1,3 @@
-use std::env;
-
 fn main() {
     // 不需要手动设置 target_os，这由 Rust 工具链自动处理
 } 
\ No newline at end of file
@@ -0,0 +1,502 @@
+use crate::{platform, AppState, DiskInfo, LoadAverage, NetworkInfo, SystemInfo, TempInfo, TempStatus};
+use crate::{CpuInfo, MemoryInfo, DisksInfo, DiskIoStats, MountIo, NetworkOverview, SensorsInfo, ProcessSummary};
+use crate::{PRESSURE_HISTORY_SIZE, TEMP_HISTORY_SIZE, TEMP_WARNING_THRESHOLD};
+use sysinfo::{CpuExt, Disk, DiskExt, System, SystemExt, ComponentExt, NetworksExt, ProcessExt, NetworkExt};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use log::{info, warn, error};
+use chrono::{DateTime, Utc};
+
+// 后台采样任务：按固定间隔刷新并发布快照，与 HTTP 请求解耦
+pub fn spawn(state: Arc<AppState>) {
+    let interval = state.sample_interval;
+    info!("Starting background collector, interval {}ms", interval.as_millis());
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+            let _gate = state.refresh_gate.lock().await;
+            let state = state.clone();
+            // 刷新是阻塞操作，放到阻塞线程池中执行
+            if let Err(e) = tokio::task::spawn_blocking(move || collect(&state)).await {
+                error!("Background collection failed: {}", e);
+            }
+        }
+    });
+}
+
+// 采样间隔：优先读取环境变量 SAMPLE_INTERVAL_MS
+pub fn sample_interval_from_env() -> Duration {
+    let ms = std::env::var("SAMPLE_INTERVAL_MS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|&v| v > 0)
+        .unwrap_or(crate::DEFAULT_SAMPLE_INTERVAL_MS);
+    Duration::from_millis(ms)
+}
+
+// 快照中可独立采集的子系统
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Subsystem {
+    Cpu,
+    Gpu,
+    Memory,
+    Disks,
+    Host,
+    Processes,
+    Sensors,
+    Network,
+    Power,
+    Performance,
+}
+
+impl Subsystem {
+    pub const ALL: [Subsystem; 10] = [
+        Subsystem::Cpu,
+        Subsystem::Gpu,
+        Subsystem::Memory,
+        Subsystem::Disks,
+        Subsystem::Host,
+        Subsystem::Processes,
+        Subsystem::Sensors,
+        Subsystem::Network,
+        Subsystem::Power,
+        Subsystem::Performance,
+    ];
+}
+
+// 刷新系统信息，生成新快照并发布到 AppState
+pub fn collect(state: &AppState) -> Arc<SystemInfo> {
+    let now = Utc::now();
+    let system_info = {
+        let mut sys = state.sys.lock();
+        // 磁盘列表由 build_disks 按需刷新
+        sys.refresh_system();
+        sys.refresh_networks();
+        sys.refresh_networks_list();
+        sys.refresh_processes();
+        build_snapshot(&mut sys, &mut state.counters.lock(), now, &|_| true)
+    };
+
+    // 记录温度警告
+    if !system_info.temp_warnings.is_empty() {
+        warn!("Temperature warnings: {:?}", &system_info.temp_warnings);
+    }
+
+    // 更新温度历史记录
+    {
+        let mut temp_history = state.temp_history.lock();
+        temp_history.push_back((now, system_info.temperatures.clone()));
+        if temp_history.len() > TEMP_HISTORY_SIZE {
+            temp_history.pop_front();
+        }
+    }
+
+    // 更新压力停滞历史记录
+    if system_info.performance_metrics.pressure.available {
+        let mut pressure_history = state.pressure_history.lock();
+        pressure_history.push_back((now, system_info.performance_metrics.pressure.clone()));
+        if pressure_history.len() > PRESSURE_HISTORY_SIZE {
+            pressure_history.pop_front();
+        }
+    }
+
+    // 发布快照
+    let snapshot = Arc::new(system_info);
+    state.publish(snapshot.clone());
+
+    snapshot
+}
+
+// 只刷新并采集 wanted 选中的子系统，其余字段保持默认值；结果不发布为快照
+pub fn collect_partial(sys: &mut System, counters: &mut platform::Counters, wanted: &dyn Fn(Subsystem) -> bool) -> SystemInfo {
+    if wanted(Subsystem::Cpu) || wanted(Subsystem::Performance) {
+        sys.refresh_cpu();
+    }
+    if wanted(Subsystem::Memory) {
+        sys.refresh_memory();
+    }
+    if wanted(Subsystem::Sensors) {
+        sys.refresh_components();
+    }
+    if wanted(Subsystem::Network) {
+        sys.refresh_networks_list();
+    }
+    if wanted(Subsystem::Processes) {
+        sys.refresh_processes();
+    }
+    build_snapshot(sys, counters, Utc::now(), wanted)
+}
+
+fn build_snapshot(
+    sys: &mut System,
+    counters: &mut platform::Counters,
+    now: DateTime<Utc>,
+    wanted: &dyn Fn(Subsystem) -> bool,
+) -> SystemInfo {
+    fn section<T: Default>(enabled: bool, build: impl FnOnce() -> T) -> T {
+        if enabled { build() } else { T::default() }
+    }
+
+    let sensors = section(wanted(Subsystem::Sensors) || wanted(Subsystem::Cpu), || build_sensors(sys));
+    let cpu = section(wanted(Subsystem::Cpu), || build_cpu(sys, &sensors.temperatures));
+    let memory = section(wanted(Subsystem::Memory), || build_memory(sys, counters));
+    let disks = section(wanted(Subsystem::Disks), || build_disks(sys, counters));
+    let network = section(wanted(Subsystem::Network), || build_network(sys, counters));
+    let processes = section(wanted(Subsystem::Processes), || build_process_summary(sys));
+    let host = wanted(Subsystem::Host);
+
+    SystemInfo {
+        // CPU
+        cpu_usage: cpu.cpu_usage,
+        cpu_temp: cpu.cpu_temp,
+        cpu_brand: cpu.cpu_brand,
+        cpu_frequency: cpu.cpu_frequency,
+        cpu_cores: cpu.cpu_cores,
+        cpu_physical_cores: cpu.cpu_physical_cores,
+        cpu_vendor_id: cpu.cpu_vendor_id,
+        cpu_load_avg: cpu.load_average.clone(),
+
+        // GPU
+        gpu_info: section(wanted(Subsystem::Gpu), platform::get_gpu_info),
+
+        // 内存
+        memory_total: memory.memory_total,
+        memory_used: memory.memory_used,
+        memory_free: memory.memory_free,
+        memory_available: memory.memory_available,
+        memory_usage: memory.memory_usage,
+        swap_total: memory.swap_total,
+        swap_used: memory.swap_used,
+        swap_free: memory.swap_free,
+        swap_usage: memory.swap_usage,
+        memory_details: memory.memory_details,
+
+        // 磁盘
+        disks: disks.disks,
+        total_disk_space: disks.total_disk_space,
+        total_disk_used: disks.total_disk_used,
+        total_disk_free: disks.total_disk_free,
+        disk_io_stats: disks.disk_io_stats,
+
+        // 系统
+        system_name: section(host, || sys.name()),
+        kernel_version: section(host, || sys.kernel_version()),
+        os_version: section(host, || sys.os_version()),
+        host_name: section(host, || sys.host_name()),
+        boot_time: section(host, || sys.boot_time()),
+        uptime: section(host, || sys.uptime()),
+        load_average: cpu.load_average,
+
+        // 进程
+        process_count: processes.process_count,
+        thread_count: processes.thread_count,
+        running_process_count: processes.running_process_count,
+        process_stats: processes.process_stats,
+
+        // 温度
+        temperatures: sensors.temperatures,
+        temp_warnings: sensors.temp_warnings,
+        timestamp: now,
+
+        // 网络
+        networks: network.networks,
+        total_rx_bytes: network.total_rx_bytes,
+        total_tx_bytes: network.total_tx_bytes,
+        network_stats: network.network_stats,
+
+        // 电源
+        power_info: section(wanted(Subsystem::Power), platform::get_power_info),
+
+        // 性能指标
+        performance_metrics: section(wanted(Subsystem::Performance), || platform::get_performance_metrics(sys, counters)),
+    }
+}
+
+// 只刷新 CPU（及传感器，用于 CPU 温度）
+pub fn refresh_cpu(sys: &mut System) -> CpuInfo {
+    sys.refresh_cpu();
+    sys.refresh_components();
+    let sensors = build_sensors(sys);
+    build_cpu(sys, &sensors.temperatures)
+}
+
+pub fn refresh_memory(sys: &mut System, counters: &mut platform::Counters) -> MemoryInfo {
+    sys.refresh_memory();
+    build_memory(sys, counters)
+}
+
+pub fn refresh_disks(sys: &mut System, counters: &mut platform::Counters) -> DisksInfo {
+    build_disks(sys, counters)
+}
+
+pub fn refresh_network(sys: &mut System, counters: &mut platform::Counters) -> NetworkOverview {
+    sys.refresh_networks_list();
+    build_network(sys, counters)
+}
+
+pub fn refresh_sensors(sys: &mut System) -> SensorsInfo {
+    sys.refresh_components();
+    build_sensors(sys)
+}
+
+pub fn refresh_process_summary(sys: &mut System) -> ProcessSummary {
+    sys.refresh_processes();
+    build_process_summary(sys)
+}
+
+fn build_cpu(sys: &System, temperatures: &[TempInfo]) -> CpuInfo {
+    // CPU温度
+    let cpu_temp = temperatures.iter()
+        .find(|t| t.label.to_lowercase().contains("cpu"))
+        .map(|t| t.temp);
+
+    CpuInfo {
+        cpu_usage: sys.cpus().iter().map(|cpu| cpu.cpu_usage()).collect(),
+        cpu_temp,
+        cpu_brand: sys.cpus().first().map(|cpu| cpu.brand().to_string()).unwrap_or_default(),
+        cpu_frequency: sys.cpus().iter().map(|cpu| cpu.frequency()).collect(),
+        cpu_cores: sys.cpus().len(),
+        cpu_physical_cores: sys.physical_core_count().unwrap_or(0),
+        cpu_vendor_id: sys.cpus().first().map(|cpu| cpu.vendor_id().to_string()).unwrap_or_default(),
+        load_average: LoadAverage {
+            one: sys.load_average().one,
+            five: sys.load_average().five,
+            fifteen: sys.load_average().fifteen,
+        },
+    }
+}
+
+fn build_memory(sys: &System, counters: &mut platform::Counters) -> MemoryInfo {
+    let memory_total = sys.total_memory();
+    let memory_used = sys.used_memory();
+    let swap_total = sys.total_swap();
+    let swap_used = sys.used_swap();
+
+    MemoryInfo {
+        memory_total,
+        memory_used,
+        memory_free: sys.free_memory(),
+        memory_available: sys.available_memory(),
+        memory_usage: (memory_used as f32 / memory_total as f32) * 100.0,
+        swap_total,
+        swap_used,
+        swap_free: sys.free_swap(),
+        swap_usage: if swap_total > 0 {
+            (swap_used as f32 / swap_total as f32) * 100.0
+        } else {
+            0.0
+        },
+        memory_details: platform::get_memory_details(counters),
+    }
+}
+
+// 默认排除的文件系统类型，可通过环境变量 DISK_EXCLUDE_FS_TYPES 覆盖（逗号分隔，留空表示不排除）
+const DEFAULT_EXCLUDED_FS_TYPES: &str = "tmpfs,devtmpfs,overlay,squashfs";
+
+struct DiskFilter {
+    fs_types: Vec<String>,
+    // 排除挂载在 /snap 下的 loop 设备，DISK_EXCLUDE_SNAP=false 时保留
+    snap_loops: bool,
+}
+
+impl DiskFilter {
+    fn from_env() -> Self {
+        let fs_types = std::env::var("DISK_EXCLUDE_FS_TYPES")
+            .unwrap_or_else(|_| DEFAULT_EXCLUDED_FS_TYPES.to_string());
+        DiskFilter {
+            fs_types: fs_types.split(',')
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect(),
+            snap_loops: std::env::var("DISK_EXCLUDE_SNAP")
+                .map_or(true, |v| v != "0" && !v.eq_ignore_ascii_case("false")),
+        }
+    }
+
+    fn excludes(&self, disk: &DiskInfo) -> bool {
+        self.fs_types.contains(&disk.file_system)
+            || (self.snap_loops && disk.name.starts_with("/dev/loop") && disk.mount_point.starts_with("/snap/"))
+    }
+}
+
+fn disk_filter() -> &'static DiskFilter {
+    static FILTER: OnceLock<DiskFilter> = OnceLock::new();
+    FILTER.get_or_init(DiskFilter::from_env)
+}
+
+// 平台不提供挂载表时退回 sysinfo 的磁盘列表
+fn sysinfo_disk(disk: &Disk) -> DiskInfo {
+    DiskInfo {
+        name: disk.name().to_string_lossy().into_owned(),
+        mount_point: disk.mount_point().to_string_lossy().into_owned(),
+        file_system: String::from_utf8_lossy(disk.file_system()).into_owned(),
+        total_space: disk.total_space(),
+        available_space: disk.available_space(),
+        ..DiskInfo::default()
+    }
+}
+
+// 平台能直接读取挂载表时不再刷新 sysinfo 的磁盘列表，避免对其中每个挂载点重复 statvfs
+fn build_disks(sys: &mut System, counters: &mut platform::Counters) -> DisksInfo {
+    let filter = disk_filter();
+    let mut disks: Vec<DiskInfo> = platform::get_filesystems().unwrap_or_else(|| {
+        sys.refresh_disks();
+        sys.disks().iter().map(sysinfo_disk).collect()
+    });
+    disks.retain(|disk| !filter.excludes(disk));
+
+    let mut total_disk_space = 0;
+    let mut total_disk_used = 0;
+    let mut total_disk_free = 0;
+    for disk in &mut disks {
+        let used = disk.total_space.saturating_sub(disk.available_space);
+        total_disk_space += disk.total_space;
+        total_disk_used += used;
+        total_disk_free += disk.available_space;
+        disk.usage_percentage = if disk.total_space > 0 {
+            used as f32 / disk.total_space as f32 * 100.0
+        } else {
+            0.0
+        };
+    }
+
+    let mut disk_io_stats = platform::get_disk_io_stats(counters);
+    link_block_devices(&mut disks, &mut disk_io_stats);
+
+    DisksInfo {
+        disks,
+        total_disk_space,
+        total_disk_used,
+        total_disk_free,
+        disk_io_stats,
+    }
+}
+
+// 通过挂载点所在的块设备及其设备栈，把文件系统和 IO 统计互相关联
+fn link_block_devices(disks: &mut [DiskInfo], io_stats: &mut [DiskIoStats]) {
+    let topology = platform::get_block_topology();
+
+    // 设备自身及其下层的全部设备
+    let lower_devices = |device: &str| {
+        let mut seen: Vec<String> = Vec::new();
+        let mut pending = vec![device.to_string()];
+        while let Some(device) = pending.pop() {
+            if seen.contains(&device) {
+                continue;
+            }
+            if let Some(node) = topology.get(&device) {
+                pending.extend(node.parents.iter().cloned());
+            }
+            seen.push(device);
+        }
+        seen
+    };
+
+    let mut stacks: Vec<(usize, Vec<String>)> = Vec::new();
+    for (i, disk) in disks.iter_mut().enumerate() {
+        let Some(device) = disk.block_device.clone() else {
+            continue;
+        };
+        let node = topology.get(&device);
+        let stack = lower_devices(&device);
+        let mut physical: Vec<String> = stack.iter()
+            .filter(|d| topology.get(*d).is_some_and(|n| n.parents.is_empty()))
+            .cloned()
+            .collect();
+        physical.sort();
+
+        disk.dm_name = node.and_then(|n| n.dm_name.clone());
+        disk.dm_type = node.and_then(|n| n.dm_type.clone());
+        disk.physical_devices = physical;
+        disk.io = io_stats.iter().find(|io| io.device == device).map(|io| MountIo {
+            read_iops: io.read_iops,
+            write_iops: io.write_iops,
+            read_bytes_sec: io.read_bytes_sec,
+            write_bytes_sec: io.write_bytes_sec,
+            io_await: io.io_await,
+            util_percentage: io.util_percentage,
+        });
+        stacks.push((i, stack));
+    }
+
+    for io in io_stats.iter_mut() {
+        if let Some(node) = topology.get(&io.device) {
+            io.dm_name = node.dm_name.clone();
+            io.dm_type = node.dm_type.clone();
+        }
+        io.mount_points = stacks.iter()
+            .filter(|(_, stack)| stack.contains(&io.device))
+            .map(|(i, _)| disks[*i].mount_point.clone())
+            .collect();
+    }
+}
+
+fn build_network(sys: &System, counters: &mut platform::Counters) -> NetworkOverview {
+    let mut addresses = platform::get_interface_addresses();
+    NetworkOverview {
+        networks: sys.networks().iter().map(|(name, data)| {
+            NetworkInfo {
+                interface: name.clone(),
+                received_bytes: data.total_received(),
+                transmitted_bytes: data.total_transmitted(),
+                received_packets: data.total_packets_received(),
+                transmitted_packets: data.total_packets_transmitted(),
+                mac_address: Some(data.mac_address().to_string()),
+                ip_addresses: addresses.remove(name).unwrap_or_default(),
+                link: platform::get_link_info(name),
+            }
+        }).collect(),
+        total_rx_bytes: sys.networks().iter().map(|(_, data)| data.total_received()).sum(),
+        total_tx_bytes: sys.networks().iter().map(|(_, data)| data.total_transmitted()).sum(),
+        network_stats: platform::get_network_stats(sys, counters),
+    }
+}
+
+// 温度信息和警告
+fn build_sensors(sys: &System) -> SensorsInfo {
+    let mut temperatures = Vec::new();
+    let mut temp_warnings = Vec::new();
+
+    for component in sys.components() {
+        let temp = component.temperature();
+        let status = if temp >= TEMP_WARNING_THRESHOLD {
+            temp_warnings.push(format!("{} temperature is too high: {:.1}°C", component.label(), temp));
+            TempStatus::Critical
+        } else if temp >= TEMP_WARNING_THRESHOLD - 10.0 {
+            temp_warnings.push(format!("{} temperature is getting high: {:.1}°C", component.label(), temp));
+            TempStatus::Warning
+        } else {
+            TempStatus::Normal
+        };
+
+        temperatures.push(TempInfo {
+            label: component.label().to_string(),
+            temp,
+            status,
+        });
+    }
+
+    SensorsInfo {
+        temperatures,
+        temp_warnings,
+    }
+}
+
+fn build_process_summary(sys: &System) -> ProcessSummary {
+    let tasks = platform::scan_tasks();
+    ProcessSummary {
+        process_count: sys.processes().len(),
+        // 平台无法统计线程时退化为进程数
+        thread_count: tasks.as_ref()
+            .map(|tasks| tasks.threads.values().sum::<u64>() as usize)
+            .unwrap_or(sys.processes().len()),
+        running_process_count: sys.processes().values()
+            .filter(|p| p.status() == sysinfo::ProcessStatus::Run)
+            .count(),
+        process_stats: platform::get_process_stats(sys, tasks.as_ref()),
+    }
+}
@@ -0,0 +1,260 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::fs;
+use std::path::{Component, Path};
+
+// sysfs 中 size 以 512 字节扇区为单位
+const SECTOR_SIZE: u64 = 512;
+
+// 单块物理磁盘，字段全部读取自 /sys/block/<name>；读取不到的为 null
+#[derive(Serialize, JsonSchema, Clone, Default)]
+pub struct BlockDeviceInventory {
+    name: String,
+    vendor: Option<String>,
+    model: Option<String>,
+    serial: Option<String>,
+    wwn: Option<String>,
+    #[schemars(extend("x-unit" = "bytes"))]
+    size: u64,
+    // false 表示 SSD 等非旋转介质
+    rotational: Option<bool>,
+    #[schemars(extend("x-unit" = "bytes"))]
+    logical_sector_size: Option<u32>,
+    #[schemars(extend("x-unit" = "bytes"))]
+    physical_sector_size: Option<u32>,
+    // 当前生效的调度器，可选的调度器见 available_schedulers
+    scheduler: Option<String>,
+    available_schedulers: Vec<String>,
+    // 设备队列深度（SCSI/SATA 的 device/queue_depth）
+    queue_depth: Option<u32>,
+    // 块层请求队列长度
+    nr_requests: Option<u32>,
+    removable: bool,
+    // sata、nvme、usb、virtio、mmc、scsi
+    transport: Option<String>,
+}
+
+fn read_value(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn read_number<T: std::str::FromStr>(path: &Path) -> Option<T> {
+    read_value(path)?.parse().ok()
+}
+
+// 依次尝试多个相对路径，不同驱动把同一属性放在不同位置
+fn read_first(dir: &Path, candidates: &[&str]) -> Option<String> {
+    candidates.iter().find_map(|candidate| read_value(&dir.join(candidate)))
+}
+
+// SCSI、SATA、SAS 和 USB 磁盘没有 serial 属性，序列号在 VPD 0x80 页中：4 字节页头之后为 ASCII 序列号
+fn read_vpd_serial(path: &Path) -> Option<String> {
+    let page = fs::read(path).ok()?;
+    let length = u16::from_be_bytes([*page.get(2)?, *page.get(3)?]) as usize;
+    let serial = page.get(4..)?;
+    let serial = String::from_utf8_lossy(&serial[..length.min(serial.len())]);
+    let serial = serial.trim_matches(|c: char| c.is_whitespace() || c == '\0');
+    (!serial.is_empty()).then(|| serial.to_string())
+}
+
+// 调度器格式为 "none [mq-deadline] kyber"，方括号内为当前调度器
+fn parse_scheduler(line: &str) -> (Option<String>, Vec<String>) {
+    let mut current = None;
+    let available = line.split_whitespace().map(|name| {
+        match name.strip_prefix('[').and_then(|n| n.strip_suffix(']')) {
+            Some(name) => {
+                current = Some(name.to_string());
+                name.to_string()
+            }
+            None => name.to_string(),
+        }
+    }).collect();
+    (current, available)
+}
+
+// 根据设备在 sysfs 设备树中的路径判断总线；USB 转 SATA 桥接盘的路径同时包含 usb 和 ata，按 usb 处理
+fn transport(name: &str, device_path: &Path) -> Option<String> {
+    let components: Vec<String> = device_path.components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect();
+    let has = |prefix: &str| components.iter().any(|c| c.starts_with(prefix));
+
+    let transport = if name.starts_with("nvme") {
+        "nvme"
+    } else if name.starts_with("mmcblk") {
+        "mmc"
+    } else if has("usb") {
+        "usb"
+    } else if has("virtio") {
+        "virtio"
+    } else if has("ata") {
+        "sata"
+    } else if has("target") {
+        "scsi"
+    } else {
+        return None;
+    };
+    Some(transport.to_string())
+}
+
+// 列出 root（通常为 /sys）下的物理磁盘；loop、zram、dm、md 等位于 devices/virtual 下的设备被跳过
+pub fn block_devices(root: &Path) -> Vec<BlockDeviceInventory> {
+    let Ok(entries) = fs::read_dir(root.join("block")) else {
+        return Vec::new();
+    };
+
+    let mut devices: Vec<BlockDeviceInventory> = entries.flatten().filter_map(|entry| {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let dir = entry.path();
+        // /sys/block 下的条目是指向设备树的符号链接
+        let real = fs::canonicalize(&dir).ok()?;
+        let device_path = real.strip_prefix(fs::canonicalize(root).ok()?).ok()?.to_path_buf();
+        if device_path.starts_with("devices/virtual") {
+            return None;
+        }
+
+        let (scheduler, available_schedulers) = read_value(&dir.join("queue/scheduler"))
+            .map(|line| parse_scheduler(&line))
+            .unwrap_or_default();
+
+        Some(BlockDeviceInventory {
+            vendor: read_value(&dir.join("device/vendor")),
+            model: read_value(&dir.join("device/model")),
+            serial: read_first(&dir, &["serial", "device/serial"])
+                .or_else(|| read_vpd_serial(&dir.join("device/vpd_pg80"))),
+            wwn: read_first(&dir, &["wwid", "device/wwid"]),
+            size: read_number::<u64>(&dir.join("size")).unwrap_or(0) * SECTOR_SIZE,
+            rotational: read_number::<u8>(&dir.join("queue/rotational")).map(|r| r == 1),
+            logical_sector_size: read_number(&dir.join("queue/logical_block_size")),
+            physical_sector_size: read_number(&dir.join("queue/physical_block_size")),
+            scheduler,
+            available_schedulers,
+            queue_depth: read_number(&dir.join("device/queue_depth")),
+            nr_requests: read_number(&dir.join("queue/nr_requests")),
+            removable: read_number::<u8>(&dir.join("removable")) == Some(1),
+            transport: transport(&name, &device_path),
+            name,
+        })
+    }).collect();
+
+    devices.sort_by(|a, b| a.name.cmp(&b.name));
+    devices
+}
+
+// 夹具依赖符号链接，只在类 Unix 平台上运行
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+    use std::path::PathBuf;
+
+    // 在临时目录中构造 sysfs 片段：设备目录位于 devices/ 下，block/<name> 为指向它的相对链接
+    struct Fixture {
+        root: PathBuf,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let root = std::env::temp_dir().join(format!("hardware_monitor_sysfs_{}", std::process::id()));
+            let _ = fs::remove_dir_all(&root);
+            fs::create_dir_all(root.join("block")).unwrap();
+            Fixture { root }
+        }
+
+        fn device(&self, name: &str, device_path: &str, files: &[(&str, &str)]) {
+            let dir = self.root.join(device_path).join("block").join(name);
+            for (file, content) in files {
+                let path = dir.join(file);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(path, format!("{}\n", content)).unwrap();
+            }
+            symlink(Path::new("..").join(device_path).join("block").join(name), self.root.join("block").join(name)).unwrap();
+        }
+
+        // 二进制属性（如 VPD 页）原样写入
+        fn raw(&self, name: &str, device_path: &str, file: &str, content: &[u8]) {
+            let path = self.root.join(device_path).join("block").join(name).join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+
+    #[test]
+    fn reads_block_devices_from_fixture() {
+        let fixture = Fixture::new();
+        fixture.device("sda", "devices/pci0000:00/0000:00:17.0/ata1/host0/target0:0:0/0:0:0:0", &[
+            ("size", "1953525168"),
+            ("removable", "0"),
+            ("device/vendor", "ATA"),
+            ("device/model", "WDC WD10EZEX-08W"),
+            ("device/wwid", "naa.50014ee2b5a1c0de"),
+            ("device/queue_depth", "32"),
+            ("queue/rotational", "1"),
+            ("queue/logical_block_size", "512"),
+            ("queue/physical_block_size", "4096"),
+            ("queue/scheduler", "none [mq-deadline] bfq"),
+            ("queue/nr_requests", "64"),
+        ]);
+        fixture.raw("sda", "devices/pci0000:00/0000:00:17.0/ata1/host0/target0:0:0/0:0:0:0", "device/vpd_pg80",
+            b"\x00\x80\x00\x14     WD-WCC6Y0ABCDEF");
+        fixture.device("nvme0n1", "devices/pci0000:00/0000:00:1d.0/0000:3d:00.0/nvme/nvme0", &[
+            ("size", "1000215216"),
+            ("removable", "0"),
+            ("wwid", "eui.0025388b91b2c3d4"),
+            ("device/model", "Samsung SSD 970 EVO Plus 500GB"),
+            ("device/serial", "S4EVNX0N123456"),
+            ("queue/rotational", "0"),
+            ("queue/logical_block_size", "512"),
+            ("queue/physical_block_size", "512"),
+            ("queue/scheduler", "[none] mq-deadline"),
+        ]);
+        fixture.device("sdb", "devices/pci0000:00/0000:00:14.0/usb2/2-1/2-1:1.0/host1/target1:0:0/1:0:0:0", &[
+            ("size", "0"),
+            ("removable", "1"),
+        ]);
+        fixture.device("loop0", "devices/virtual", &[("size", "0")]);
+
+        let devices = block_devices(&fixture.root);
+        let names: Vec<&str> = devices.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["nvme0n1", "sda", "sdb"]);
+
+        let nvme = &devices[0];
+        assert_eq!(nvme.model.as_deref(), Some("Samsung SSD 970 EVO Plus 500GB"));
+        assert_eq!(nvme.serial.as_deref(), Some("S4EVNX0N123456"));
+        assert_eq!(nvme.wwn.as_deref(), Some("eui.0025388b91b2c3d4"));
+        assert_eq!(nvme.size, 1000215216 * 512);
+        assert_eq!(nvme.rotational, Some(false));
+        assert_eq!(nvme.scheduler.as_deref(), Some("none"));
+        assert_eq!(nvme.queue_depth, None);
+        assert_eq!(nvme.transport.as_deref(), Some("nvme"));
+
+        let sata = &devices[1];
+        assert_eq!(sata.vendor.as_deref(), Some("ATA"));
+        assert_eq!(sata.serial.as_deref(), Some("WD-WCC6Y0ABCDEF"));
+        assert_eq!(sata.wwn.as_deref(), Some("naa.50014ee2b5a1c0de"));
+        assert_eq!(sata.rotational, Some(true));
+        assert_eq!(sata.logical_sector_size, Some(512));
+        assert_eq!(sata.physical_sector_size, Some(4096));
+        assert_eq!(sata.scheduler.as_deref(), Some("mq-deadline"));
+        assert_eq!(sata.available_schedulers, ["none", "mq-deadline", "bfq"]);
+        assert_eq!(sata.queue_depth, Some(32));
+        assert_eq!(sata.nr_requests, Some(64));
+        assert_eq!(sata.transport.as_deref(), Some("sata"));
+
+        let usb = &devices[2];
+        assert!(usb.removable);
+        assert_eq!(usb.model, None);
+        assert_eq!(usb.transport.as_deref(), Some("usb"));
+    }
+}
@@ -1,29 +1,51 @@
-use actix_web::{get, web, App, HttpServer, Responder, HttpResponse, Error as ActixError};
+use actix_web::{get, web, App, HttpServer, HttpRequest, Responder, HttpResponse, Error as ActixError};
+use actix_web::http::header;
 use actix_cors::Cors;
-use serde::Serialize;
-use sysinfo::{CpuExt, DiskExt, System, SystemExt, ComponentExt, NetworksExt, ProcessExt, NetworkExt};
-use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use sysinfo::{System, SystemExt};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 use parking_lot::Mutex;
+use arc_swap::ArcSwapOption;
+use tokio::sync::watch;
 use log::{info, warn, error};
 use chrono::{DateTime, Utc};
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 use std::env;
 use std::net::TcpListener;
 
+mod collector;
+mod inventory;
+mod metrics;
+mod openapi;
 mod platform;
+mod processes;
+mod projection;
+mod stream;
+mod v2;
+mod ws;
+
+use projection::{FieldSelector, ProjectionQuery};
 
 const TEMP_HISTORY_SIZE: usize = 60;
+const PRESSURE_HISTORY_SIZE: usize = 60;
+const SNAPSHOT_HISTORY_SIZE: usize = 60;
 const TEMP_WARNING_THRESHOLD: f32 = 80.0;
 const CACHE_DURATION_MS: u64 = 1000;
+const DEFAULT_SAMPLE_INTERVAL_MS: u64 = 1000;
 const DEFAULT_PORT: u16 = 9527;
 const MAX_PORT_ATTEMPTS: u16 = 100;
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, JsonSchema, Clone, Default)]
 struct SystemInfo {
     // CPU 相关信息
+    #[schemars(extend("x-unit" = "%"))]
     cpu_usage: Vec<f32>,
+    #[schemars(extend("x-unit" = "°C"))]
     cpu_temp: Option<f32>,
     cpu_brand: String,
+    #[schemars(extend("x-unit" = "MHz"))]
     cpu_frequency: Vec<u64>,
     cpu_cores: usize,
     cpu_physical_cores: usize,
@@ -34,20 +56,33 @@ struct SystemInfo {
     gpu_info: Option<GpuInfo>,
 
     // 内存相关信息
+    #[schemars(extend("x-unit" = "bytes"))]
     memory_total: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
     memory_used: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
     memory_free: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
     memory_available: u64,
+    #[schemars(extend("x-unit" = "%"))]
     memory_usage: f32,
+    #[schemars(extend("x-unit" = "bytes"))]
     swap_total: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
     swap_used: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
     swap_free: u64,
+    #[schemars(extend("x-unit" = "%"))]
     swap_usage: f32,
+    memory_details: Option<MemoryDetails>,
 
     // 磁盘相关信息
     disks: Vec<DiskInfo>,
+    #[schemars(extend("x-unit" = "bytes"))]
     total_disk_space: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
     total_disk_used: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
     total_disk_free: u64,
     disk_io_stats: Vec<DiskIoStats>,
 
@@ -56,7 +91,9 @@ struct SystemInfo {
     kernel_version: Option<String>,
     os_version: Option<String>,
     host_name: Option<String>,
+    #[schemars(extend("x-unit" = "s"))]
     boot_time: u64,
+    #[schemars(extend("x-unit" = "s"))]
     uptime: u64,
     load_average: LoadAverage,
 
@@ -73,7 +110,9 @@ struct SystemInfo {
 
     // 网络信息
     networks: Vec<NetworkInfo>,
+    #[schemars(extend("x-unit" = "bytes"))]
     total_rx_bytes: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
     total_tx_bytes: u64,
     network_stats: NetworkStats,
 
@@ -84,345 +123,1118 @@ struct SystemInfo {
     performance_metrics: PerformanceMetrics,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, JsonSchema, Clone, Default)]
 struct DiskInfo {
     name: String,
     mount_point: String,
+    file_system: String,
+    // 无法获取挂载选项的平台为 null
+    read_only: Option<bool>,
+    mount_options: Vec<String>,
+    #[schemars(extend("x-unit" = "bytes"))]
     total_space: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
     available_space: u64,
+    // 容量为 0 的挂载点为 0
+    #[schemars(extend("x-unit" = "%"))]
     usage_percentage: f32,
+    // 来自 statvfs；不使用固定 inode 表的文件系统（如 btrfs）为 null
+    inodes_total: Option<u64>,
+    inodes_used: Option<u64>,
+    inodes_free: Option<u64>,
+    // 挂载源对应的内核设备名，如 dm-3、nvme0n1p2
+    block_device: Option<String>,
+    dm_name: Option<String>,
+    dm_type: Option<String>,
+    // 设备栈最底层的磁盘
+    physical_devices: Vec<String>,
+    io: Option<MountIo>,
+}
+
+// 挂载点所在块设备的 IO，取自 disk_io_stats 中的同名设备
+#[derive(Serialize, JsonSchema, Clone, Default)]
+struct MountIo {
+    #[schemars(extend("x-unit" = "1/s"))]
+    read_iops: Option<f64>,
+    #[schemars(extend("x-unit" = "1/s"))]
+    write_iops: Option<f64>,
+    #[schemars(extend("x-unit" = "bytes/s"))]
+    read_bytes_sec: Option<f64>,
+    #[schemars(extend("x-unit" = "bytes/s"))]
+    write_bytes_sec: Option<f64>,
+    #[schemars(extend("x-unit" = "ms"))]
+    io_await: Option<f64>,
+    #[schemars(extend("x-unit" = "%"))]
+    util_percentage: Option<f64>,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, JsonSchema, Clone, Default)]
 struct TempInfo {
     label: String,
+    #[schemars(extend("x-unit" = "°C"))]
     temp: f32,
     status: TempStatus,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, JsonSchema, Clone, Default)]
 enum TempStatus {
+    #[default]
     Normal,
     Warning,
     Critical,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema, Default)]
 struct HealthStatus {
     status: String,
     timestamp: DateTime<Utc>,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, JsonSchema, Clone, Default)]
 struct NetworkInfo {
     interface: String,
+    #[schemars(extend("x-unit" = "bytes"))]
     received_bytes: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
     transmitted_bytes: u64,
     received_packets: u64,
     transmitted_packets: u64,
     mac_address: Option<String>,
+    // "地址/前缀长度" 格式，包含 IPv4 和 IPv6
     ip_addresses: Vec<String>,
+    link: Option<LinkInfo>,
 }
 
-#[derive(Serialize, Clone)]
+// 链路状态，来自 /sys/class/net
+#[derive(Serialize, JsonSchema, Clone, Default)]
+struct LinkInfo {
+    // up / down / dormant / lowerlayerdown / unknown 等
+    operstate: String,
+    // 接口未启用时无法读取
+    carrier: Option<bool>,
+    #[schemars(extend("x-unit" = "bytes"))]
+    mtu: Option<u32>,
+    // 未协商或虚拟网卡为 null
+    #[schemars(extend("x-unit" = "Mbit/s"))]
+    speed: Option<u32>,
+    duplex: Option<String>,
+    driver: Option<String>,
+}
+
+#[derive(Serialize, JsonSchema, Clone, Default)]
 struct LoadAverage {
     one: f64,
     five: f64,
     fifteen: f64,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, JsonSchema, Clone, Default)]
 struct GpuInfo {
     vendor: String,
     model: String,
+    #[schemars(extend("x-unit" = "%"))]
     usage: f32,
+    #[schemars(extend("x-unit" = "bytes"))]
     memory_total: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
     memory_used: u64,
+    #[schemars(extend("x-unit" = "°C"))]
     temperature: Option<f32>,
+    #[schemars(extend("x-unit" = "W"))]
     power_usage: Option<f32>,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, JsonSchema, Clone, Default)]
 struct DiskIoStats {
     device: String,
+    partition: bool,
+    dm_name: Option<String>,
+    dm_type: Option<String>,
+    // 直接或经由分区、device-mapper 位于该设备上的挂载点
+    mount_points: Vec<String>,
     reads: u64,
     writes: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
     read_bytes: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
     write_bytes: u64,
+    #[schemars(extend("x-unit" = "ms"))]
     read_time: u64,
+    #[schemars(extend("x-unit" = "ms"))]
     write_time: u64,
+    // 当前在途的请求数
     io_in_progress: u64,
+    // 设备忙碌的累计时间
+    #[schemars(extend("x-unit" = "ms"))]
+    io_time: u64,
+    #[schemars(extend("x-unit" = "ms"))]
+    weighted_io_time: u64,
+    // discard 字段自内核 4.18 起提供，flush 字段自 5.5 起提供
+    discards: Option<u64>,
+    #[schemars(extend("x-unit" = "bytes"))]
+    discard_bytes: Option<u64>,
+    #[schemars(extend("x-unit" = "ms"))]
+    discard_time: Option<u64>,
+    flushes: Option<u64>,
+    #[schemars(extend("x-unit" = "ms"))]
+    flush_time: Option<u64>,
+    // 以下为两次采样间的值，首次采样为 null
+    #[schemars(extend("x-unit" = "1/s"))]
+    read_iops: Option<f64>,
+    #[schemars(extend("x-unit" = "1/s"))]
+    write_iops: Option<f64>,
+    #[schemars(extend("x-unit" = "1/s"))]
+    discard_iops: Option<f64>,
+    #[schemars(extend("x-unit" = "1/s"))]
+    flush_iops: Option<f64>,
+    #[schemars(extend("x-unit" = "bytes/s"))]
+    read_bytes_sec: Option<f64>,
+    #[schemars(extend("x-unit" = "bytes/s"))]
+    write_bytes_sec: Option<f64>,
+    // 每个请求的平均耗时（含排队），期间没有完成的请求时为 null
+    #[schemars(extend("x-unit" = "ms"))]
+    read_await: Option<f64>,
+    #[schemars(extend("x-unit" = "ms"))]
+    write_await: Option<f64>,
+    #[schemars(extend("x-unit" = "ms"))]
+    io_await: Option<f64>,
+    #[schemars(extend("x-unit" = "%"))]
+    util_percentage: Option<f64>,
+    // 平均队列深度（aqu-sz）
+    queue_depth: Option<f64>,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, JsonSchema, Clone, Default)]
 struct ProcessStats {
     zombie_count: usize,
     sleeping_count: usize,
     blocked_count: usize,
+    #[schemars(extend("x-unit" = "%"))]
     total_cpu_usage: f32,
+    #[schemars(extend("x-unit" = "bytes"))]
     total_memory_usage: u64,
+    // 以下仅 Linux 提供
+    task_states: Option<TaskStates>,
+    // /proc/stat 中可运行和阻塞在 IO 上的任务数
+    procs_running: Option<u64>,
+    procs_blocked: Option<u64>,
+}
+
+// 按 /proc/[pid]/task/[tid]/stat 状态字母统计的任务（线程）数
+#[derive(Serialize, JsonSchema, Clone, Default)]
+struct TaskStates {
+    running: usize,
+    sleeping: usize,
+    disk_sleep: usize,
+    stopped: usize,
+    tracing_stop: usize,
+    zombie: usize,
+    idle: usize,
+    dead: usize,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, JsonSchema, Clone, Default)]
 struct NetworkStats {
+    // 包含 IPv4 和 IPv6 套接字
     tcp_connections: usize,
     udp_connections: usize,
+    // 去重后的端口号，详细的监听地址见 listeners
     tcp_listen_ports: Vec<u16>,
     udp_listen_ports: Vec<u16>,
+    listeners: Vec<Listener>,
+    tcp_states: Option<TcpStates>,
+    protocol_counters: Option<ProtocolCounters>,
     interface_stats: Vec<InterfaceStats>,
 }
 
-#[derive(Serialize, Clone)]
+// 各状态的 TCP 套接字数（IPv4 和 IPv6 合计）
+#[derive(Serialize, JsonSchema, Clone, Default)]
+struct TcpStates {
+    established: usize,
+    syn_sent: usize,
+    syn_recv: usize,
+    fin_wait1: usize,
+    fin_wait2: usize,
+    time_wait: usize,
+    close: usize,
+    close_wait: usize,
+    last_ack: usize,
+    listen: usize,
+    closing: usize,
+    new_syn_recv: usize,
+}
+
+// 协议栈计数器，来自 /proc/net/snmp、/proc/net/netstat 和 /proc/net/sockstat；
+// 累计值自系统启动起计，速率在首次采样时为 null
+#[derive(Serialize, JsonSchema, Clone, Default)]
+struct ProtocolCounters {
+    tcp_out_segs: u64,
+    tcp_retrans_segs: u64,
+    tcp_out_rsts: u64,
+    tcp_estab_resets: u64,
+    tcp_attempt_fails: u64,
+    tcp_listen_overflows: u64,
+    tcp_listen_drops: u64,
+    udp_in_errors: u64,
+    udp_rcvbuf_errors: u64,
+    udp_no_ports: u64,
+    #[schemars(extend("x-unit" = "1/s"))]
+    tcp_retrans_segs_sec: Option<f64>,
+    #[schemars(extend("x-unit" = "1/s"))]
+    tcp_out_rsts_sec: Option<f64>,
+    #[schemars(extend("x-unit" = "1/s"))]
+    tcp_estab_resets_sec: Option<f64>,
+    #[schemars(extend("x-unit" = "1/s"))]
+    tcp_attempt_fails_sec: Option<f64>,
+    #[schemars(extend("x-unit" = "1/s"))]
+    tcp_listen_overflows_sec: Option<f64>,
+    #[schemars(extend("x-unit" = "1/s"))]
+    tcp_listen_drops_sec: Option<f64>,
+    #[schemars(extend("x-unit" = "1/s"))]
+    udp_in_errors_sec: Option<f64>,
+    #[schemars(extend("x-unit" = "1/s"))]
+    udp_rcvbuf_errors_sec: Option<f64>,
+    #[schemars(extend("x-unit" = "1/s"))]
+    udp_no_ports_sec: Option<f64>,
+    // 两次采样间重传段占发送段的比例，期间没有发送时为 null
+    #[schemars(extend("x-unit" = "%"))]
+    tcp_retrans_percentage: Option<f64>,
+    sockets_used: u64,
+    tcp_inuse: u64,
+    tcp_orphan: u64,
+    tcp_time_wait: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    tcp_memory: u64,
+    udp_inuse: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    udp_memory: u64,
+}
+
+// 单个套接字及其所属进程；无权读取 /proc/[pid]/fd 时 pid 为 null
+#[derive(Serialize, JsonSchema, Clone, Default)]
+struct Connection {
+    // tcp 或 udp
+    protocol: String,
+    local_address: String,
+    local_port: u16,
+    remote_address: String,
+    remote_port: u16,
+    // TCP 状态名（如 LISTEN、ESTABLISHED）；UDP 为 ESTABLISHED 或 UNCONN
+    state: String,
+    #[schemars(extend("x-unit" = "bytes"))]
+    rx_queue: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    tx_queue: u64,
+    uid: u32,
+    inode: u64,
+    pid: Option<u32>,
+    process_name: Option<String>,
+}
+
+// TCP 处于 LISTEN 状态或未连接的 UDP 套接字
+#[derive(Serialize, JsonSchema, Clone, Default, PartialEq, Eq)]
+struct Listener {
+    // tcp 或 udp
+    protocol: String,
+    address: String,
+    port: u16,
+}
+
+#[derive(Serialize, JsonSchema, Clone, Default)]
 struct InterfaceStats {
     name: String,
     rx_errors: u64,
     tx_errors: u64,
     rx_dropped: u64,
     tx_dropped: u64,
-    rx_bytes_sec: f64,
-    tx_bytes_sec: f64,
-    rx_packets_sec: f64,
-    tx_packets_sec: f64,
+    // 首次采样或新出现的网卡没有速率；v1 为保持兼容输出 0，v2 输出 null
+    #[serde(serialize_with = "rate_or_zero")]
+    #[schemars(with = "f64", extend("x-unit" = "bytes/s"))]
+    rx_bytes_sec: Option<f64>,
+    #[serde(serialize_with = "rate_or_zero")]
+    #[schemars(with = "f64", extend("x-unit" = "bytes/s"))]
+    tx_bytes_sec: Option<f64>,
+    #[serde(serialize_with = "rate_or_zero")]
+    #[schemars(with = "f64", extend("x-unit" = "packets/s"))]
+    rx_packets_sec: Option<f64>,
+    #[serde(serialize_with = "rate_or_zero")]
+    #[schemars(with = "f64", extend("x-unit" = "packets/s"))]
+    tx_packets_sec: Option<f64>,
+}
+
+fn rate_or_zero<S: serde::Serializer>(rate: &Option<f64>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(rate.unwrap_or(0.0))
 }
 
-#[derive(Serialize, Clone)]
+fn percent_or_zero<S: serde::Serializer>(percent: &Option<f32>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f32(percent.unwrap_or(0.0))
+}
+
+#[derive(Serialize, JsonSchema, Clone, Default)]
 struct PowerInfo {
     ac_powered: bool,
     battery_present: bool,
+    #[schemars(extend("x-unit" = "%"))]
     battery_percentage: Option<f32>,
+    #[schemars(extend("x-unit" = "s"))]
     battery_time_remaining: Option<u64>,
+    #[schemars(extend("x-unit" = "W"))]
     power_consumption: Option<f32>,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, JsonSchema, Clone, Default)]
 struct PerformanceMetrics {
-    iowait_percentage: f32,
-    steal_percentage: f32,
-    system_percentage: f32,
-    user_percentage: f32,
-    nice_percentage: f32,
-    irq_percentage: f32,
-    softirq_percentage: f32,
+    // 各类 CPU 时间占比，首次采样或计数器回退时没有基线为 None；v1 为保持兼容输出 0，v2 输出 null
+    #[serde(serialize_with = "percent_or_zero")]
+    #[schemars(with = "f32", extend("x-unit" = "%"))]
+    iowait_percentage: Option<f32>,
+    #[serde(serialize_with = "percent_or_zero")]
+    #[schemars(with = "f32", extend("x-unit" = "%"))]
+    steal_percentage: Option<f32>,
+    #[serde(serialize_with = "percent_or_zero")]
+    #[schemars(with = "f32", extend("x-unit" = "%"))]
+    system_percentage: Option<f32>,
+    #[serde(serialize_with = "percent_or_zero")]
+    #[schemars(with = "f32", extend("x-unit" = "%"))]
+    user_percentage: Option<f32>,
+    #[serde(serialize_with = "percent_or_zero")]
+    #[schemars(with = "f32", extend("x-unit" = "%"))]
+    nice_percentage: Option<f32>,
+    #[serde(serialize_with = "percent_or_zero")]
+    #[schemars(with = "f32", extend("x-unit" = "%"))]
+    irq_percentage: Option<f32>,
+    #[serde(serialize_with = "percent_or_zero")]
+    #[schemars(with = "f32", extend("x-unit" = "%"))]
+    softirq_percentage: Option<f32>,
+    #[serde(serialize_with = "percent_or_zero")]
+    #[schemars(with = "f32", extend("x-unit" = "%"))]
+    guest_percentage: Option<f32>,
+    #[serde(serialize_with = "percent_or_zero")]
+    #[schemars(with = "f32", extend("x-unit" = "%"))]
+    idle_percentage: Option<f32>,
     cpu_queue_length: u64,
+    // 开机以来的累计值
     context_switches: u64,
     interrupts: u64,
+    // 两次采样之间的速率，首次采样时为 null
+    #[schemars(extend("x-unit" = "1/s"))]
+    context_switches_per_sec: Option<f64>,
+    #[schemars(extend("x-unit" = "1/s"))]
+    interrupts_per_sec: Option<f64>,
+    per_core: Vec<CoreTimes>,
+    pressure: PressureInfo,
+}
+
+// 压力停滞信息（PSI），来自 /proc/pressure；内核不支持或未启用 PSI 时 available 为 false，各资源为 null
+#[derive(Serialize, JsonSchema, Clone, Default)]
+struct PressureInfo {
+    available: bool,
+    cpu: Option<ResourcePressure>,
+    memory: Option<ResourcePressure>,
+    io: Option<ResourcePressure>,
+}
+
+// some 为至少一个任务因该资源停滞的时间占比，full 为所有非空闲任务同时停滞的占比；
+// 5.13 之前的内核没有 CPU 的 full 行
+#[derive(Serialize, JsonSchema, Clone, Default)]
+struct ResourcePressure {
+    some: PressureStall,
+    full: Option<PressureStall>,
+}
+
+#[derive(Serialize, JsonSchema, Clone, Default)]
+struct PressureStall {
+    // 10 秒、60 秒和 300 秒窗口内的平均值
+    #[schemars(extend("x-unit" = "%"))]
+    avg10: f64,
+    #[schemars(extend("x-unit" = "%"))]
+    avg60: f64,
+    #[schemars(extend("x-unit" = "%"))]
+    avg300: f64,
+    // 开机以来的累计停滞时间
+    #[schemars(extend("x-unit" = "us"))]
+    total: u64,
+}
+
+#[derive(Serialize, JsonSchema, Clone, Default)]
+struct CoreTimes {
+    cpu: usize,
+    // 没有基线时为 null
+    #[schemars(extend("x-unit" = "%"))]
+    user_percentage: Option<f32>,
+    #[schemars(extend("x-unit" = "%"))]
+    nice_percentage: Option<f32>,
+    #[schemars(extend("x-unit" = "%"))]
+    system_percentage: Option<f32>,
+    #[schemars(extend("x-unit" = "%"))]
+    idle_percentage: Option<f32>,
+    #[schemars(extend("x-unit" = "%"))]
+    iowait_percentage: Option<f32>,
+    #[schemars(extend("x-unit" = "%"))]
+    irq_percentage: Option<f32>,
+    #[schemars(extend("x-unit" = "%"))]
+    softirq_percentage: Option<f32>,
+    #[schemars(extend("x-unit" = "%"))]
+    steal_percentage: Option<f32>,
+    #[schemars(extend("x-unit" = "%"))]
+    guest_percentage: Option<f32>,
+    // 来自 /proc/schedstat 的调度次数，内核未开启 schedstats 或 kernel.sched_schedstats=0 时为 null
+    #[schemars(extend("x-unit" = "1/s"))]
+    context_switches_per_sec: Option<f64>,
+    #[schemars(extend("x-unit" = "1/s"))]
+    interrupts_per_sec: Option<f64>,
+}
+
+// 单个进程的详细信息，读取失败（如权限不足）的字段为 null
+#[derive(Serialize, JsonSchema, Clone, Default)]
+struct ProcessDetail {
+    pid: u32,
+    cmdline: Option<Vec<String>>,
+    cwd: Option<String>,
+    exe: Option<String>,
+    // 默认隐藏变量值
+    environment: Option<BTreeMap<String, String>>,
+    fd_count: Option<usize>,
+    fd_limit: Option<ResourceLimit>,
+    io: Option<ProcessIo>,
+    cgroups: Option<Vec<ProcessCgroup>>,
+    // 命名空间类型到 inode 编号
+    namespaces: Option<BTreeMap<String, u64>>,
+    limits: Option<BTreeMap<String, ResourceLimit>>,
+    voluntary_ctxt_switches: Option<u64>,
+    nonvoluntary_ctxt_switches: Option<u64>,
+    #[schemars(extend("x-unit" = "bytes"))]
+    memory_pss: Option<u64>,
+    #[schemars(extend("x-unit" = "bytes"))]
+    memory_uss: Option<u64>,
+}
+
+// null 表示 unlimited
+#[derive(Serialize, JsonSchema, Clone, Default)]
+struct ResourceLimit {
+    soft: Option<u64>,
+    hard: Option<u64>,
+}
+
+#[derive(Serialize, JsonSchema, Clone, Default)]
+struct ProcessIo {
+    #[schemars(extend("x-unit" = "bytes"))]
+    rchar: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    wchar: u64,
+    syscr: u64,
+    syscw: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    read_bytes: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    write_bytes: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    cancelled_write_bytes: u64,
+}
+
+#[derive(Serialize, JsonSchema, Clone, Default)]
+struct ProcessCgroup {
+    hierarchy: u32,
+    controllers: Vec<String>,
+    path: String,
+}
+
+// 各子系统的独立响应，字段与 SystemInfo 中对应部分一致
+#[derive(Serialize, JsonSchema, Clone, Default)]
+struct CpuInfo {
+    #[schemars(extend("x-unit" = "%"))]
+    cpu_usage: Vec<f32>,
+    #[schemars(extend("x-unit" = "°C"))]
+    cpu_temp: Option<f32>,
+    cpu_brand: String,
+    #[schemars(extend("x-unit" = "MHz"))]
+    cpu_frequency: Vec<u64>,
+    cpu_cores: usize,
+    cpu_physical_cores: usize,
+    cpu_vendor_id: String,
+    load_average: LoadAverage,
+}
+
+#[derive(Serialize, JsonSchema, Clone, Default)]
+struct MemoryInfo {
+    #[schemars(extend("x-unit" = "bytes"))]
+    memory_total: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    memory_used: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    memory_free: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    memory_available: u64,
+    #[schemars(extend("x-unit" = "%"))]
+    memory_usage: f32,
+    #[schemars(extend("x-unit" = "bytes"))]
+    swap_total: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    swap_used: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    swap_free: u64,
+    #[schemars(extend("x-unit" = "%"))]
+    swap_usage: f32,
+    memory_details: Option<MemoryDetails>,
+}
+
+// 内存明细，来自 /proc/meminfo 和 /proc/vmstat；累计值自系统启动起计，速率在首次采样时为 null
+#[derive(Serialize, JsonSchema, Clone, Default)]
+struct MemoryDetails {
+    #[schemars(extend("x-unit" = "bytes"))]
+    buffers: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    cached: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    shmem: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    slab: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    slab_reclaimable: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    slab_unreclaimable: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    dirty: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    writeback: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    mapped: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    anon: u64,
+    // 已承诺分配的内存，超过 commit_limit 时是否拒绝分配取决于 vm.overcommit_memory
+    #[schemars(extend("x-unit" = "bytes"))]
+    committed_as: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    commit_limit: u64,
+    // 预留的大页数量
+    hugepages_total: u64,
+    hugepages_free: u64,
+    hugepages_reserved: u64,
+    hugepages_surplus: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    hugepage_size: u64,
+    // 透明大页（THP）占用的匿名内存
+    #[schemars(extend("x-unit" = "bytes"))]
+    anon_hugepages: u64,
+    page_faults: u64,
+    major_faults: u64,
+    // 换入换出的页数
+    swap_in: u64,
+    swap_out: u64,
+    // 内核低于 4.13 时没有 oom_kill 计数
+    oom_kills: Option<u64>,
+    #[schemars(extend("x-unit" = "1/s"))]
+    page_faults_sec: Option<f64>,
+    #[schemars(extend("x-unit" = "1/s"))]
+    major_faults_sec: Option<f64>,
+    #[schemars(extend("x-unit" = "pages/s"))]
+    swap_in_sec: Option<f64>,
+    #[schemars(extend("x-unit" = "pages/s"))]
+    swap_out_sec: Option<f64>,
+    #[schemars(extend("x-unit" = "1/s"))]
+    oom_kills_sec: Option<f64>,
+}
+
+#[derive(Serialize, JsonSchema, Clone, Default)]
+struct DisksInfo {
+    disks: Vec<DiskInfo>,
+    #[schemars(extend("x-unit" = "bytes"))]
+    total_disk_space: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    total_disk_used: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    total_disk_free: u64,
+    disk_io_stats: Vec<DiskIoStats>,
+}
+
+#[derive(Serialize, JsonSchema, Clone, Default)]
+struct NetworkOverview {
+    networks: Vec<NetworkInfo>,
+    #[schemars(extend("x-unit" = "bytes"))]
+    total_rx_bytes: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    total_tx_bytes: u64,
+    network_stats: NetworkStats,
+}
+
+#[derive(Serialize, JsonSchema, Clone, Default)]
+struct SensorsInfo {
+    temperatures: Vec<TempInfo>,
+    temp_warnings: Vec<String>,
+}
+
+#[derive(Serialize, JsonSchema, Clone, Default)]
+struct ProcessSummary {
+    process_count: usize,
+    thread_count: usize,
+    running_process_count: usize,
+    process_stats: ProcessStats,
+}
+
+impl SystemInfo {
+    fn cpu(&self) -> CpuInfo {
+        CpuInfo {
+            cpu_usage: self.cpu_usage.clone(),
+            cpu_temp: self.cpu_temp,
+            cpu_brand: self.cpu_brand.clone(),
+            cpu_frequency: self.cpu_frequency.clone(),
+            cpu_cores: self.cpu_cores,
+            cpu_physical_cores: self.cpu_physical_cores,
+            cpu_vendor_id: self.cpu_vendor_id.clone(),
+            load_average: self.load_average.clone(),
+        }
+    }
+
+    fn memory(&self) -> MemoryInfo {
+        MemoryInfo {
+            memory_total: self.memory_total,
+            memory_used: self.memory_used,
+            memory_free: self.memory_free,
+            memory_available: self.memory_available,
+            memory_usage: self.memory_usage,
+            swap_total: self.swap_total,
+            swap_used: self.swap_used,
+            swap_free: self.swap_free,
+            swap_usage: self.swap_usage,
+            memory_details: self.memory_details.clone(),
+        }
+    }
+
+    fn disks(&self) -> DisksInfo {
+        DisksInfo {
+            disks: self.disks.clone(),
+            total_disk_space: self.total_disk_space,
+            total_disk_used: self.total_disk_used,
+            total_disk_free: self.total_disk_free,
+            disk_io_stats: self.disk_io_stats.clone(),
+        }
+    }
+
+    fn network(&self) -> NetworkOverview {
+        NetworkOverview {
+            networks: self.networks.clone(),
+            total_rx_bytes: self.total_rx_bytes,
+            total_tx_bytes: self.total_tx_bytes,
+            network_stats: self.network_stats.clone(),
+        }
+    }
+
+    fn sensors(&self) -> SensorsInfo {
+        SensorsInfo {
+            temperatures: self.temperatures.clone(),
+            temp_warnings: self.temp_warnings.clone(),
+        }
+    }
+
+    fn process_summary(&self) -> ProcessSummary {
+        ProcessSummary {
+            process_count: self.process_count,
+            thread_count: self.thread_count,
+            running_process_count: self.running_process_count,
+            process_stats: self.process_stats.clone(),
+        }
+    }
 }
 
 struct AppState {
+    // 后台采集使用的实例；CPU 使用率按两次刷新的差值计算，只能由后台采集刷新
     sys: Mutex<System>,
+    // 子系统接口和投影请求在快照过期时按需刷新的独立实例
+    ondemand_sys: Mutex<System>,
+    // 上一次采样的累计计数器，用于计算速率，只由后台采集推进；加锁顺序在 sys 之后
+    counters: Mutex<platform::Counters>,
     temp_history: Mutex<VecDeque<(DateTime<Utc>, Vec<TempInfo>)>>,
-    last_update: Mutex<DateTime<Utc>>,
-    cached_info: Mutex<Option<SystemInfo>>,
+    // 内核不支持 PSI 时保持为空
+    pressure_history: Mutex<VecDeque<(DateTime<Utc>, PressureInfo)>>,
+    // 最新快照，读取方无需加锁
+    snapshot: ArcSwapOption<SystemInfo>,
+    // 保证同一时刻只有一次刷新，并发的缓存未命中共享这次刷新结果
+    refresh_gate: tokio::sync::Mutex<()>,
+    // 最近发布的快照及其序号，供事件流续传
+    recent_snapshots: Mutex<VecDeque<(u64, Arc<SystemInfo>)>>,
+    // 最新快照序号，发布新快照时通知订阅者
+    updates: watch::Sender<u64>,
+    sample_interval: Duration,
 }
 
 impl AppState {
-    fn new() -> Self {
+    fn new(sample_interval: Duration) -> Self {
         AppState {
             sys: Mutex::new(System::new_all()),
+            ondemand_sys: Mutex::new(System::new_all()),
+            counters: Mutex::new(platform::Counters::default()),
             temp_history: Mutex::new(VecDeque::with_capacity(TEMP_HISTORY_SIZE)),
-            last_update: Mutex::new(Utc::now()),
-            cached_info: Mutex::new(None),
+            pressure_history: Mutex::new(VecDeque::with_capacity(PRESSURE_HISTORY_SIZE)),
+            snapshot: ArcSwapOption::empty(),
+            refresh_gate: tokio::sync::Mutex::new(()),
+            recent_snapshots: Mutex::new(VecDeque::with_capacity(SNAPSHOT_HISTORY_SIZE)),
+            updates: watch::channel(0).0,
+            sample_interval,
         }
     }
-}
 
-#[get("/api/system")]
-async fn get_system_info(data: web::Data<Arc<AppState>>) -> Result<HttpResponse, ActixError> {
-    let now = Utc::now();
-    let mut cached_info = data.cached_info.lock();
-    let last_update = *data.last_update.lock();
-
-    if let Some(info) = cached_info.as_ref() {
-        if (now - last_update).num_milliseconds() < CACHE_DURATION_MS as i64 {
-            return Ok(HttpResponse::Ok()
-                .insert_header(("X-Cache-Status", "Hit"))
-                .json(info));
+    // 发布新快照；调用方需持有 refresh_gate 以保证序号递增
+    fn publish(&self, info: Arc<SystemInfo>) {
+        self.snapshot.store(Some(info.clone()));
+        let id = *self.updates.borrow() + 1;
+        {
+            let mut recent = self.recent_snapshots.lock();
+            recent.push_back((id, info));
+            if recent.len() > SNAPSHOT_HISTORY_SIZE {
+                recent.pop_front();
+            }
         }
+        self.updates.send_replace(id);
     }
 
-    let mut sys = data.sys.lock();
-    sys.refresh_all();
-    sys.refresh_components();
-    sys.refresh_networks();
-    sys.refresh_networks_list();
-    sys.refresh_processes();
-
-    // CPU信息
-    let cpu_usage: Vec<f32> = sys.cpus().iter().map(|cpu| cpu.cpu_usage()).collect();
-    let cpu_frequency: Vec<u64> = sys.cpus().iter().map(|cpu| cpu.frequency()).collect();
-    let cpu_brand = sys.cpus().first().map(|cpu| cpu.brand().to_string()).unwrap_or_default();
-    let cpu_vendor_id = sys.cpus().first().map(|cpu| cpu.vendor_id().to_string()).unwrap_or_default();
-    let cpu_cores = sys.cpus().len();
-    let cpu_physical_cores = sys.physical_core_count().unwrap_or(0);
-
-    // 温度信息和警告
-    let mut temperatures = Vec::new();
-    let mut temp_warnings = Vec::new();
-    
-    for component in sys.components() {
-        let temp = component.temperature();
-        let status = if temp >= TEMP_WARNING_THRESHOLD {
-            temp_warnings.push(format!("{} temperature is too high: {:.1}°C", component.label(), temp));
-            TempStatus::Critical
-        } else if temp >= TEMP_WARNING_THRESHOLD - 10.0 {
-            temp_warnings.push(format!("{} temperature is getting high: {:.1}°C", component.label(), temp));
-            TempStatus::Warning
-        } else {
-            TempStatus::Normal
-        };
-
-        temperatures.push(TempInfo {
-            label: component.label().to_string(),
-            temp,
-            status,
-        });
+    // 按需刷新基于后台基线的副本计算速率，不回写，避免轮询把后台的采样窗口缩短到几毫秒
+    fn counters_baseline(&self) -> platform::Counters {
+        self.counters.lock().clone()
     }
 
-    // 记录温度警告
-    if !temp_warnings.is_empty() {
-        warn!("Temperature warnings: {:?}", &temp_warnings);
+    fn latest_snapshot_entry(&self) -> Option<(u64, Arc<SystemInfo>)> {
+        self.recent_snapshots.lock().back().cloned()
     }
 
-    // 更新温度历史记录
-    let mut temp_history = data.temp_history.lock();
-    temp_history.push_back((now, temperatures.clone()));
-    if temp_history.len() > TEMP_HISTORY_SIZE {
-        temp_history.pop_front();
+    // 序号大于 last_id 的快照；序号比当前还新（服务已重启）时只返回最新快照
+    fn snapshots_since(&self, last_id: u64) -> VecDeque<(u64, Arc<SystemInfo>)> {
+        let recent = self.recent_snapshots.lock();
+        match recent.back() {
+            Some((latest, info)) if *latest < last_id => VecDeque::from([(*latest, info.clone())]),
+            _ => recent.iter().filter(|(id, _)| *id > last_id).cloned().collect(),
+        }
     }
 
-    // CPU温度
-    let cpu_temp = temperatures.iter()
-        .find(|t| t.label.to_lowercase().contains("cpu"))
-        .map(|t| t.temp);
-
-    // 内存信息
-    let memory_total = sys.total_memory();
-    let memory_used = sys.used_memory();
-    let memory_free = sys.free_memory();
-    let memory_available = sys.available_memory();
-    let memory_usage = (memory_used as f32 / memory_total as f32) * 100.0;
-    let swap_total = sys.total_swap();
-    let swap_used = sys.used_swap();
-    let swap_free = sys.free_swap();
-    let swap_usage = if swap_total > 0 {
-        (swap_used as f32 / swap_total as f32) * 100.0
-    } else {
-        0.0
-    };
+    // 返回不超过 max_age 的最新快照
+    fn recent_snapshot(&self, max_age: Duration) -> Option<Arc<SystemInfo>> {
+        self.snapshot.load_full().filter(|info| {
+            (Utc::now() - info.timestamp).to_std().unwrap_or_default() < max_age
+        })
+    }
+
+    // 返回最新快照；超过一个采样周期加缓存时长仍未更新则视为过期
+    fn fresh_snapshot(&self) -> Option<Arc<SystemInfo>> {
+        self.recent_snapshot(self.sample_interval + Duration::from_millis(CACHE_DURATION_MS))
+    }
 
-    // 磁盘信息
-    let mut total_disk_space = 0;
-    let mut total_disk_used = 0;
-    let mut total_disk_free = 0;
-    let disks: Vec<DiskInfo> = sys.disks().iter().map(|disk| {
-        let total = disk.total_space();
-        let available = disk.available_space();
-        let used = total - available;
-        total_disk_space += total;
-        total_disk_used += used;
-        total_disk_free += available;
-        DiskInfo {
-            name: disk.name().to_string_lossy().into_owned(),
-            mount_point: disk.mount_point().to_string_lossy().into_owned(),
-            total_space: total,
-            available_space: available,
-            usage_percentage: (used as f32 / total as f32) * 100.0,
+    // 返回新鲜快照，必要时刷新一次；第二个返回值表示是否命中缓存
+    async fn snapshot_or_refresh(self: &Arc<Self>) -> Result<(Arc<SystemInfo>, bool), ActixError> {
+        if let Some(info) = self.fresh_snapshot() {
+            return Ok((info, true));
         }
-    }).collect();
 
-    // 系统信息
-    let system_info = SystemInfo {
-        // CPU
-        cpu_usage,
-        cpu_temp,
-        cpu_brand,
-        cpu_frequency,
-        cpu_cores,
-        cpu_physical_cores,
-        cpu_vendor_id,
-        cpu_load_avg: LoadAverage {
-            one: sys.load_average().one,
-            five: sys.load_average().five,
-            fifteen: sys.load_average().fifteen,
-        },
-
-        // GPU
-        gpu_info: platform::get_gpu_info(),
-
-        // 内存
-        memory_total,
-        memory_used,
-        memory_free,
-        memory_available,
-        memory_usage,
-        swap_total,
-        swap_used,
-        swap_free,
-        swap_usage,
-
-        // 磁盘
-        disks,
-        total_disk_space,
-        total_disk_used,
-        total_disk_free,
-        disk_io_stats: platform::get_disk_io_stats(),
-
-        // 系统
-        system_name: sys.name(),
-        kernel_version: sys.kernel_version(),
-        os_version: sys.os_version(),
-        host_name: sys.host_name(),
-        boot_time: sys.boot_time(),
-        uptime: sys.uptime(),
-        load_average: LoadAverage {
-            one: sys.load_average().one,
-            five: sys.load_average().five,
-            fifteen: sys.load_average().fifteen,
-        },
-
-        // 进程
-        process_count: sys.processes().len(),
-        thread_count: sys.processes().len(),
-        running_process_count: sys.processes().values()
-            .filter(|p| p.status() == sysinfo::ProcessStatus::Run)
-            .count(),
-        process_stats: platform::get_process_stats(&sys),
-
-        // 温度
-        temperatures,
-        temp_warnings: temp_warnings.clone(),
-        timestamp: now,
-
-        // 网络
-        networks: sys.networks().iter().map(|(name, data)| {
-            NetworkInfo {
-                interface: name.clone(),
-                received_bytes: data.total_received(),
-                transmitted_bytes: data.total_transmitted(),
-                received_packets: data.total_packets_received(),
-                transmitted_packets: data.total_packets_transmitted(),
-                mac_address: Some(data.mac_address().to_string()),
-                ip_addresses: Vec::new(),
-            }
-        }).collect(),
-        total_rx_bytes: sys.networks().iter().map(|(_, data)| data.total_received()).sum(),
-        total_tx_bytes: sys.networks().iter().map(|(_, data)| data.total_transmitted()).sum(),
-        network_stats: platform::get_network_stats(&sys),
+        let _gate = self.refresh_gate.lock().await;
+        // 等待期间其他请求或后台任务可能已经完成刷新
+        if let Some(info) = self.fresh_snapshot() {
+            return Ok((info, true));
+        }
 
-        // 电源
-        power_info: platform::get_power_info(),
+        let state = self.clone();
+        let info = web::block(move || collector::collect(&state)).await?;
+        Ok((info, false))
+    }
+}
 
-        // 性能指标
-        performance_metrics: platform::get_performance_metrics(&sys),
+#[get("/api/system")]
+async fn get_system_info(
+    data: web::Data<Arc<AppState>>,
+    query: web::Query<ProjectionQuery>,
+) -> Result<HttpResponse, ActixError> {
+    let selector = FieldSelector::from_query(&query).map_err(actix_web::error::ErrorBadRequest)?;
+    if selector.is_identity() {
+        let (info, hit) = data.snapshot_or_refresh().await?;
+        return Ok(HttpResponse::Ok()
+            .insert_header(("X-Cache-Status", if hit { "Hit" } else { "Miss" }))
+            .json(&*info));
+    }
+
+    let (value, hit) = match data.fresh_snapshot() {
+        Some(info) => (serde_json::to_value(&*info)?, true),
+        None => {
+            // 只采集被选中字段涉及的子系统
+            let wanted = selector.wanted_subsystems();
+            let state = data.get_ref().clone();
+            let info = web::block(move || {
+                collector::collect_partial(&mut state.ondemand_sys.lock(), &mut state.counters_baseline(), &|s| wanted.contains(&s))
+            }).await?;
+            (serde_json::to_value(&info)?, false)
+        }
     };
 
-    // 更新缓存
-    *cached_info = Some(system_info.clone());
-    *data.last_update.lock() = now;
+    Ok(HttpResponse::Ok()
+        .insert_header(("X-Cache-Status", if hit { "Hit" } else { "Miss" }))
+        .json(selector.apply(value)))
+}
 
+// v2 接口；/api/system 保持原有格式不变
+#[get("/api/v2/system")]
+async fn get_system_info_v2(data: web::Data<Arc<AppState>>) -> Result<HttpResponse, ActixError> {
+    let (info, hit) = data.snapshot_or_refresh().await?;
+    Ok(HttpResponse::Ok()
+        .insert_header(("X-Cache-Status", if hit { "Hit" } else { "Miss" }))
+        .json(v2::SystemInfoV2::from_snapshot(&info)))
+}
+
+// 子系统接口：快照足够新时直接取对应部分，否则只刷新该子系统
+async fn serve_section<T, F, R>(data: &web::Data<Arc<AppState>>, from_snapshot: F, refresh: R) -> Result<HttpResponse, ActixError>
+where
+    T: Serialize + Send + 'static,
+    F: FnOnce(&SystemInfo) -> T,
+    R: FnOnce(&mut System, &mut platform::Counters) -> T + Send + 'static,
+{
+    if let Some(info) = data.recent_snapshot(Duration::from_millis(CACHE_DURATION_MS)) {
+        return Ok(HttpResponse::Ok()
+            .insert_header(("X-Cache-Status", "Hit"))
+            .json(from_snapshot(&info)));
+    }
+
+    let state = data.get_ref().clone();
+    let section = web::block(move || refresh(&mut state.ondemand_sys.lock(), &mut state.counters_baseline())).await?;
     Ok(HttpResponse::Ok()
         .insert_header(("X-Cache-Status", "Miss"))
-        .json(system_info))
+        .json(section))
+}
+
+#[get("/api/cpu")]
+async fn get_cpu_info(data: web::Data<Arc<AppState>>) -> Result<HttpResponse, ActixError> {
+    serve_section(&data, SystemInfo::cpu, |sys, _| collector::refresh_cpu(sys)).await
+}
+
+#[get("/api/memory")]
+async fn get_memory_info(data: web::Data<Arc<AppState>>) -> Result<HttpResponse, ActixError> {
+    serve_section(&data, SystemInfo::memory, collector::refresh_memory).await
+}
+
+#[derive(Deserialize)]
+struct DisksQuery {
+    // 为 false 时磁盘 IO 统计只包含整盘
+    partitions: Option<bool>,
+}
+
+#[get("/api/disks")]
+async fn get_disks_info(data: web::Data<Arc<AppState>>, query: web::Query<DisksQuery>) -> Result<HttpResponse, ActixError> {
+    let partitions = query.partitions.unwrap_or(true);
+    let filter = move |mut disks: DisksInfo| {
+        if !partitions {
+            disks.disk_io_stats.retain(|io| !io.partition);
+        }
+        disks
+    };
+    serve_section(
+        &data,
+        move |info| filter(info.disks()),
+        move |sys, counters| filter(collector::refresh_disks(sys, counters)),
+    ).await
+}
+
+#[get("/api/network")]
+async fn get_network_info(data: web::Data<Arc<AppState>>) -> Result<HttpResponse, ActixError> {
+    serve_section(&data, SystemInfo::network, collector::refresh_network).await
+}
+
+#[get("/api/sensors")]
+async fn get_sensors_info(data: web::Data<Arc<AppState>>) -> Result<HttpResponse, ActixError> {
+    serve_section(&data, SystemInfo::sensors, |sys, _| collector::refresh_sensors(sys)).await
+}
+
+#[get("/api/power")]
+async fn get_power_info(data: web::Data<Arc<AppState>>) -> Result<HttpResponse, ActixError> {
+    serve_section(&data, |info| info.power_info.clone(), |_, _| platform::get_power_info()).await
+}
+
+#[get("/api/processes/summary")]
+async fn get_process_summary(data: web::Data<Arc<AppState>>) -> Result<HttpResponse, ActixError> {
+    serve_section(&data, SystemInfo::process_summary, |sys, _| collector::refresh_process_summary(sys)).await
+}
+
+// 在进程表上执行查询；快照足够新时直接使用后台任务刷新过的进程表，否则刷新按需实例的进程表，
+// 第二个返回值表示是否命中
+async fn with_processes<T, F>(data: &web::Data<Arc<AppState>>, query: F) -> Result<(T, bool), ActixError>
+where
+    T: Send + 'static,
+    F: FnOnce(&System) -> T + Send + 'static,
+{
+    let hit = data.recent_snapshot(Duration::from_millis(CACHE_DURATION_MS)).is_some();
+    let state = data.get_ref().clone();
+    let result = web::block(move || {
+        if hit {
+            return query(&state.sys.lock());
+        }
+        let mut sys = state.ondemand_sys.lock();
+        sys.refresh_processes();
+        query(&sys)
+    }).await?;
+    Ok((result, hit))
+}
+
+#[get("/api/processes")]
+async fn list_processes(
+    data: web::Data<Arc<AppState>>,
+    query: web::Query<processes::ProcessQuery>,
+) -> Result<HttpResponse, ActixError> {
+    let listing = processes::ProcessListing::from_query(&query).map_err(actix_web::error::ErrorBadRequest)?;
+    let (list, hit) = with_processes(&data, move |sys| listing.apply(sys)).await?;
+
+    Ok(HttpResponse::Ok()
+        .insert_header(("X-Cache-Status", if hit { "Hit" } else { "Miss" }))
+        .json(list))
+}
+
+#[get("/api/processes/tree")]
+async fn get_process_tree(
+    data: web::Data<Arc<AppState>>,
+    query: web::Query<processes::ProcessTreeQuery>,
+) -> Result<HttpResponse, ActixError> {
+    let query = query.into_inner();
+    // 线程数需要遍历 /proc，在持有进程表的锁之前读取
+    let threads = web::block(platform::get_thread_counts).await?;
+    let (tree, hit) = with_processes(&data, move |sys| processes::build_tree(sys, &query, threads)).await?;
+
+    match tree {
+        Some(tree) => Ok(HttpResponse::Ok()
+            .insert_header(("X-Cache-Status", if hit { "Hit" } else { "Miss" }))
+            .json(tree)),
+        None => Ok(HttpResponse::NotFound().body("No matching root process")),
+    }
+}
+
+#[derive(Deserialize)]
+struct ProcessDetailQuery {
+    show_env: Option<bool>,
+}
+
+// 环境变量常含令牌和密码，只有服务端设置 PROCESS_SHOW_ENV=true 时才允许 show_env 返回原值
+fn show_env_allowed() -> bool {
+    static ALLOWED: OnceLock<bool> = OnceLock::new();
+    *ALLOWED.get_or_init(|| {
+        env::var("PROCESS_SHOW_ENV").is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+    })
+}
+
+#[get("/api/processes/{pid}")]
+async fn get_process_detail(
+    pid: web::Path<u32>,
+    query: web::Query<ProcessDetailQuery>,
+) -> Result<HttpResponse, ActixError> {
+    if !platform::CAPABILITIES.process_detail {
+        return Ok(HttpResponse::NotImplemented().body("Process details are not supported on this platform"));
+    }
+
+    let pid = pid.into_inner();
+    let show_env = query.show_env.unwrap_or(false);
+    if show_env && !show_env_allowed() {
+        return Ok(HttpResponse::Forbidden().body("Environment values are disabled, set PROCESS_SHOW_ENV=true on the server to allow show_env"));
+    }
+    match web::block(move || platform::get_process_detail(pid, show_env)).await? {
+        Some(detail) => Ok(HttpResponse::Ok().json(detail)),
+        None => Ok(HttpResponse::NotFound().body(format!("No such process: {}", pid))),
+    }
+}
+
+#[derive(Deserialize)]
+struct ConnectionQuery {
+    // 不区分大小写
+    state: Option<String>,
+    // 匹配本地或远端端口
+    port: Option<u16>,
+    protocol: Option<String>,
+}
+
+impl ConnectionQuery {
+    fn matches(&self, conn: &Connection) -> bool {
+        self.state.as_ref().is_none_or(|state| state.eq_ignore_ascii_case(&conn.state))
+            && self.port.is_none_or(|port| conn.local_port == port || conn.remote_port == port)
+            && self.protocol.as_ref().is_none_or(|protocol| protocol.eq_ignore_ascii_case(&conn.protocol))
+    }
+}
+
+#[get("/api/network/connections")]
+async fn get_connections(query: web::Query<ConnectionQuery>) -> Result<HttpResponse, ActixError> {
+    if !platform::CAPABILITIES.connections {
+        return Ok(HttpResponse::NotImplemented().body("Connection listing is not supported on this platform"));
+    }
+
+    let query = query.into_inner();
+    let connections = web::block(move || platform::get_connections(|conn| query.matches(conn))).await?;
+    Ok(HttpResponse::Ok().json(connections))
+}
+
+#[get("/api/inventory/block")]
+async fn get_block_inventory() -> Result<HttpResponse, ActixError> {
+    if !platform::CAPABILITIES.block_inventory {
+        return Ok(HttpResponse::NotImplemented().body("Block device inventory is not supported on this platform"));
+    }
+
+    let devices = web::block(|| inventory::block_devices(std::path::Path::new("/sys"))).await?;
+    Ok(HttpResponse::Ok().json(devices))
+}
+
+#[get("/metrics")]
+async fn get_metrics(req: HttpRequest, data: web::Data<Arc<AppState>>) -> Result<HttpResponse, ActixError> {
+    let (info, _) = data.snapshot_or_refresh().await?;
+    let openmetrics = req.headers()
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.contains("application/openmetrics-text"));
+    let content_type = if openmetrics {
+        metrics::OPENMETRICS_CONTENT_TYPE
+    } else {
+        metrics::TEXT_CONTENT_TYPE
+    };
+
+    Ok(HttpResponse::Ok()
+        .content_type(content_type)
+        .body(metrics::render(&info, openmetrics)))
+}
+
+#[derive(Deserialize)]
+struct StreamQuery {
+    min_interval_ms: Option<u64>,
+}
+
+#[get("/api/stream")]
+async fn stream_snapshots(
+    req: HttpRequest,
+    data: web::Data<Arc<AppState>>,
+    projection: web::Query<ProjectionQuery>,
+    query: web::Query<StreamQuery>,
+) -> Result<HttpResponse, ActixError> {
+    let selector = FieldSelector::from_query(&projection).map_err(actix_web::error::ErrorBadRequest)?;
+    let min_interval = Duration::from_millis(query.min_interval_ms.unwrap_or(0));
+    let last_event_id = req.headers()
+        .get("Last-Event-ID")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok());
+
+    let events = stream::snapshot_events(data.get_ref().clone(), selector, min_interval, last_event_id);
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        .streaming(events))
+}
+
+#[get("/api/ws")]
+async fn ws_connect(
+    req: HttpRequest,
+    body: web::Payload,
+    data: web::Data<Arc<AppState>>,
+) -> Result<HttpResponse, ActixError> {
+    let (response, session, messages) = actix_ws::handle(&req, body)?;
+    actix_web::rt::spawn(ws::run(data.get_ref().clone(), session, messages));
+    Ok(response)
+}
+
+#[get("/api/openapi.json")]
+async fn get_openapi() -> impl Responder {
+    HttpResponse::Ok().json(openapi::document())
+}
+
+#[get("/api/schemas/{name}")]
+async fn get_json_schema(name: web::Path<String>) -> impl Responder {
+    match openapi::json_schema(&name) {
+        Some(schema) => HttpResponse::Ok().json(schema),
+        None => HttpResponse::NotFound().body(format!("Unknown type: {}", name)),
+    }
 }
 
 #[get("/api/health")]
@@ -440,6 +1252,12 @@ async fn get_temp_history(data: web::Data<Arc<AppState>>) -> impl Responder {
     HttpResponse::Ok().json(&*temp_history)
 }
 
+#[get("/api/pressure/history")]
+async fn get_pressure_history(data: web::Data<Arc<AppState>>) -> impl Responder {
+    let pressure_history = data.pressure_history.lock();
+    HttpResponse::Ok().json(&*pressure_history)
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     // 初始化日志
@@ -447,7 +1265,8 @@ async fn main() -> std::io::Result<()> {
 
     info!("Initializing hardware monitoring service...");
 
-    let app_state = Arc::new(AppState::new());
+    let app_state = Arc::new(AppState::new(collector::sample_interval_from_env()));
+    collector::spawn(app_state.clone());
     let app_state = web::Data::new(app_state);
 
     // 获取环境变量中的端口，如果没有则使用默认端口
@@ -498,8 +1317,27 @@ async fn main() -> std::io::Result<()> {
             .wrap(cors)
             .app_data(app_state.clone())
             .service(get_system_info)
+            .service(get_system_info_v2)
+            .service(get_cpu_info)
+            .service(get_memory_info)
+            .service(get_disks_info)
+            .service(get_network_info)
+            .service(get_connections)
+            .service(get_sensors_info)
+            .service(get_power_info)
+            .service(get_process_summary)
+            .service(list_processes)
+            .service(get_process_tree)
+            .service(get_process_detail)
+            .service(get_block_inventory)
+            .service(get_metrics)
+            .service(stream_snapshots)
+            .service(ws_connect)
+            .service(get_openapi)
+            .service(get_json_schema)
             .service(health_check)
             .service(get_temp_history)
+            .service(get_pressure_history)
     })
     .listen(listener)?
     .run()
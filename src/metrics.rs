@@ -0,0 +1,628 @@
+use crate::{DiskIoStats, PressureStall, SystemInfo};
+use std::fmt::Write;
+
+const PREFIX: &str = "hardware_monitor_";
+
+// 从单个设备的统计中取出可选的采样间指标
+type DiskValue = fn(&DiskIoStats) -> Option<f64>;
+
+pub const TEXT_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+pub const OPENMETRICS_CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Gauge,
+    Counter,
+}
+
+// Prometheus 文本格式编码器，同时支持 0.0.4 文本格式和 OpenMetrics
+struct Encoder {
+    out: String,
+    openmetrics: bool,
+    // 当前指标族的样本名
+    sample_name: String,
+}
+
+impl Encoder {
+    fn new(openmetrics: bool) -> Self {
+        Encoder {
+            out: String::new(),
+            openmetrics,
+            sample_name: String::new(),
+        }
+    }
+
+    // 写入指标族的元数据；name 不含单位后缀，unit 会按 OpenMetrics 规范拼接到名称末尾
+    fn family(&mut self, kind: Kind, name: &str, unit: &str, help: &str) -> &mut Self {
+        let mut family = format!("{}{}", PREFIX, name);
+        if !unit.is_empty() {
+            family.push('_');
+            family.push_str(unit);
+        }
+        let (type_name, sample_name) = match kind {
+            Kind::Gauge => (family.clone(), family.clone()),
+            // OpenMetrics 中计数器族名不带 _total，样本名带；0.0.4 格式两者一致
+            Kind::Counter if self.openmetrics => (family.clone(), format!("{}_total", family)),
+            Kind::Counter => (format!("{}_total", family), format!("{}_total", family)),
+        };
+        let kind = match kind {
+            Kind::Gauge => "gauge",
+            Kind::Counter => "counter",
+        };
+
+        let _ = writeln!(self.out, "# HELP {} {}", type_name, help);
+        let _ = writeln!(self.out, "# TYPE {} {}", type_name, kind);
+        if !unit.is_empty() {
+            let _ = writeln!(self.out, "# UNIT {} {}", type_name, unit);
+        }
+        self.sample_name = sample_name;
+        self
+    }
+
+    fn gauge(&mut self, name: &str, unit: &str, help: &str) -> &mut Self {
+        self.family(Kind::Gauge, name, unit, help)
+    }
+
+    fn counter(&mut self, name: &str, unit: &str, help: &str) -> &mut Self {
+        self.family(Kind::Counter, name, unit, help)
+    }
+
+    fn sample(&mut self, labels: &[(&str, &str)], value: f64) -> &mut Self {
+        self.out.push_str(&self.sample_name);
+        if !labels.is_empty() {
+            self.out.push('{');
+            for (i, (key, value)) in labels.iter().enumerate() {
+                if i > 0 {
+                    self.out.push(',');
+                }
+                let _ = write!(self.out, "{}=\"{}\"", key, escape_label(value));
+            }
+            self.out.push('}');
+        }
+        let _ = writeln!(self.out, " {}", format_value(value));
+        self
+    }
+
+    fn value(&mut self, value: f64) -> &mut Self {
+        self.sample(&[], value)
+    }
+
+    fn finish(mut self) -> String {
+        if self.openmetrics {
+            self.out.push_str("# EOF\n");
+        }
+        self.out
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn bool_value(value: bool) -> f64 {
+    if value { 1.0 } else { 0.0 }
+}
+
+// 将快照中的所有数值字段渲染为 Prometheus 指标
+pub fn render(info: &SystemInfo, openmetrics: bool) -> String {
+    let mut m = Encoder::new(openmetrics);
+
+    // CPU
+    m.gauge("cpu_usage", "percent", "CPU usage per logical core");
+    for (i, usage) in info.cpu_usage.iter().enumerate() {
+        m.sample(&[("cpu", &i.to_string())], *usage as f64);
+    }
+    m.gauge("cpu_frequency", "hertz", "Current CPU frequency per logical core");
+    for (i, freq) in info.cpu_frequency.iter().enumerate() {
+        m.sample(&[("cpu", &i.to_string())], *freq as f64 * 1_000_000.0);
+    }
+    if let Some(temp) = info.cpu_temp {
+        m.gauge("cpu_temperature", "celsius", "CPU temperature").value(temp as f64);
+    }
+    m.gauge("cpu_cores", "", "Number of logical CPU cores").value(info.cpu_cores as f64);
+    m.gauge("cpu_physical_cores", "", "Number of physical CPU cores").value(info.cpu_physical_cores as f64);
+    m.gauge("load_average", "", "System load average")
+        .sample(&[("window", "1m")], info.load_average.one)
+        .sample(&[("window", "5m")], info.load_average.five)
+        .sample(&[("window", "15m")], info.load_average.fifteen);
+
+    // GPU
+    if let Some(gpu) = &info.gpu_info {
+        let labels = [("vendor", gpu.vendor.as_str()), ("model", gpu.model.as_str())];
+        m.gauge("gpu_usage", "percent", "GPU utilization").sample(&labels, gpu.usage as f64);
+        m.gauge("gpu_memory_total", "bytes", "Total GPU memory").sample(&labels, gpu.memory_total as f64);
+        m.gauge("gpu_memory_used", "bytes", "Used GPU memory").sample(&labels, gpu.memory_used as f64);
+        if let Some(temp) = gpu.temperature {
+            m.gauge("gpu_temperature", "celsius", "GPU temperature").sample(&labels, temp as f64);
+        }
+        if let Some(power) = gpu.power_usage {
+            m.gauge("gpu_power", "watts", "GPU power draw").sample(&labels, power as f64);
+        }
+    }
+
+    // 内存
+    m.gauge("memory_total", "bytes", "Total physical memory").value(info.memory_total as f64);
+    m.gauge("memory_used", "bytes", "Used physical memory").value(info.memory_used as f64);
+    m.gauge("memory_free", "bytes", "Free physical memory").value(info.memory_free as f64);
+    m.gauge("memory_available", "bytes", "Available physical memory").value(info.memory_available as f64);
+    m.gauge("memory_usage", "percent", "Physical memory usage").value(info.memory_usage as f64);
+    m.gauge("swap_total", "bytes", "Total swap space").value(info.swap_total as f64);
+    m.gauge("swap_used", "bytes", "Used swap space").value(info.swap_used as f64);
+    m.gauge("swap_free", "bytes", "Free swap space").value(info.swap_free as f64);
+    m.gauge("swap_usage", "percent", "Swap usage").value(info.swap_usage as f64);
+    if let Some(details) = &info.memory_details {
+        for (name, help, value) in [
+            ("memory_buffers", "Memory used by block device buffers", details.buffers),
+            ("memory_cached", "Memory used by the page cache", details.cached),
+            ("memory_shmem", "Shared memory and tmpfs", details.shmem),
+            ("memory_slab", "Kernel slab memory", details.slab),
+            ("memory_slab_reclaimable", "Reclaimable kernel slab memory", details.slab_reclaimable),
+            ("memory_slab_unreclaimable", "Unreclaimable kernel slab memory", details.slab_unreclaimable),
+            ("memory_dirty", "Memory waiting to be written back to disk", details.dirty),
+            ("memory_writeback", "Memory being written back to disk", details.writeback),
+            ("memory_mapped", "Files mapped into memory", details.mapped),
+            ("memory_anon", "Anonymous memory", details.anon),
+            ("memory_committed", "Memory committed by allocations", details.committed_as),
+            ("memory_commit_limit", "Commit limit under strict overcommit", details.commit_limit),
+            ("memory_hugepage_size", "Default huge page size", details.hugepage_size),
+            ("memory_anon_hugepages", "Anonymous memory backed by transparent huge pages", details.anon_hugepages),
+        ] {
+            m.gauge(name, "bytes", help).value(value as f64);
+        }
+        m.gauge("memory_hugepages", "", "Huge pages by state");
+        for (state, count) in [
+            ("total", details.hugepages_total),
+            ("free", details.hugepages_free),
+            ("reserved", details.hugepages_reserved),
+            ("surplus", details.hugepages_surplus),
+        ] {
+            m.sample(&[("state", state)], count as f64);
+        }
+        for (name, help, total, rate) in [
+            ("memory_page_faults", "Page faults", Some(details.page_faults), details.page_faults_sec),
+            ("memory_major_page_faults", "Major page faults", Some(details.major_faults), details.major_faults_sec),
+            ("swap_in_pages", "Pages swapped in", Some(details.swap_in), details.swap_in_sec),
+            ("swap_out_pages", "Pages swapped out", Some(details.swap_out), details.swap_out_sec),
+            ("oom_kills", "Processes killed by the OOM killer", details.oom_kills, details.oom_kills_sec),
+        ] {
+            if let Some(total) = total {
+                m.counter(name, "", help).value(total as f64);
+            }
+            if let Some(rate) = rate {
+                m.gauge(&format!("{}_rate", name), "", &format!("{} per second", help)).value(rate);
+            }
+        }
+    }
+
+    // 磁盘
+    m.gauge("disk_total", "bytes", "Filesystem size");
+    for disk in &info.disks {
+        m.sample(&[("device", &disk.name), ("mount_point", &disk.mount_point)], disk.total_space as f64);
+    }
+    m.gauge("disk_available", "bytes", "Filesystem space available");
+    for disk in &info.disks {
+        m.sample(&[("device", &disk.name), ("mount_point", &disk.mount_point)], disk.available_space as f64);
+    }
+    m.gauge("disk_usage", "percent", "Filesystem usage");
+    for disk in &info.disks {
+        m.sample(&[("device", &disk.name), ("mount_point", &disk.mount_point)], disk.usage_percentage as f64);
+    }
+    m.gauge("disk_inodes", "", "Filesystem inodes");
+    for disk in &info.disks {
+        if let Some(inodes) = disk.inodes_total {
+            m.sample(&[("device", &disk.name), ("mount_point", &disk.mount_point)], inodes as f64);
+        }
+    }
+    m.gauge("disk_inodes_free", "", "Filesystem inodes free");
+    for disk in &info.disks {
+        if let Some(inodes) = disk.inodes_free {
+            m.sample(&[("device", &disk.name), ("mount_point", &disk.mount_point)], inodes as f64);
+        }
+    }
+    m.gauge("disk_read_only", "", "Whether the filesystem is mounted read-only");
+    for disk in &info.disks {
+        if let Some(read_only) = disk.read_only {
+            m.sample(&[("device", &disk.name), ("mount_point", &disk.mount_point)], bool_value(read_only));
+        }
+    }
+    m.gauge("disk_info", "", "Filesystem metadata, value is always 1");
+    for disk in &info.disks {
+        m.sample(&[
+            ("device", &disk.name),
+            ("mount_point", &disk.mount_point),
+            ("fs_type", &disk.file_system),
+            ("block_device", disk.block_device.as_deref().unwrap_or("")),
+            ("dm_name", disk.dm_name.as_deref().unwrap_or("")),
+        ], 1.0);
+    }
+    m.gauge("disks_total", "bytes", "Total size of all filesystems").value(info.total_disk_space as f64);
+    m.gauge("disks_used", "bytes", "Used space of all filesystems").value(info.total_disk_used as f64);
+    m.gauge("disks_free", "bytes", "Free space of all filesystems").value(info.total_disk_free as f64);
+
+    // 磁盘 IO
+    m.counter("disk_reads_completed", "", "Reads completed");
+    for io in &info.disk_io_stats {
+        m.sample(&[("device", &io.device)], io.reads as f64);
+    }
+    m.counter("disk_writes_completed", "", "Writes completed");
+    for io in &info.disk_io_stats {
+        m.sample(&[("device", &io.device)], io.writes as f64);
+    }
+    m.counter("disk_read", "bytes", "Bytes read");
+    for io in &info.disk_io_stats {
+        m.sample(&[("device", &io.device)], io.read_bytes as f64);
+    }
+    m.counter("disk_written", "bytes", "Bytes written");
+    for io in &info.disk_io_stats {
+        m.sample(&[("device", &io.device)], io.write_bytes as f64);
+    }
+    m.counter("disk_read_time", "seconds", "Time spent reading");
+    for io in &info.disk_io_stats {
+        m.sample(&[("device", &io.device)], io.read_time as f64 / 1000.0);
+    }
+    m.counter("disk_write_time", "seconds", "Time spent writing");
+    for io in &info.disk_io_stats {
+        m.sample(&[("device", &io.device)], io.write_time as f64 / 1000.0);
+    }
+    m.gauge("disk_io_in_progress", "", "I/O requests currently in flight");
+    for io in &info.disk_io_stats {
+        m.sample(&[("device", &io.device)], io.io_in_progress as f64);
+    }
+    m.counter("disk_io_time", "seconds", "Time the device was busy");
+    for io in &info.disk_io_stats {
+        m.sample(&[("device", &io.device)], io.io_time as f64 / 1000.0);
+    }
+    m.counter("disk_io_time_weighted", "seconds", "Time spent doing I/O weighted by the number of requests in flight");
+    for io in &info.disk_io_stats {
+        m.sample(&[("device", &io.device)], io.weighted_io_time as f64 / 1000.0);
+    }
+    m.counter("disk_discards_completed", "", "Discards completed");
+    for io in &info.disk_io_stats {
+        if let Some(discards) = io.discards {
+            m.sample(&[("device", &io.device)], discards as f64);
+        }
+    }
+    m.counter("disk_discarded", "bytes", "Bytes discarded");
+    for io in &info.disk_io_stats {
+        if let Some(bytes) = io.discard_bytes {
+            m.sample(&[("device", &io.device)], bytes as f64);
+        }
+    }
+    m.counter("disk_flushes_completed", "", "Flush requests completed");
+    for io in &info.disk_io_stats {
+        if let Some(flushes) = io.flushes {
+            m.sample(&[("device", &io.device)], flushes as f64);
+        }
+    }
+    let rates: [(&str, &str, &str, DiskValue); 8] = [
+        ("disk_read_iops", "", "Reads completed per second", |io| io.read_iops),
+        ("disk_write_iops", "", "Writes completed per second", |io| io.write_iops),
+        ("disk_read_rate", "bytes_per_second", "Read throughput", |io| io.read_bytes_sec),
+        ("disk_write_rate", "bytes_per_second", "Write throughput", |io| io.write_bytes_sec),
+        ("disk_read_await", "seconds", "Average time per read including queueing", |io| io.read_await.map(|ms| ms / 1000.0)),
+        ("disk_write_await", "seconds", "Average time per write including queueing", |io| io.write_await.map(|ms| ms / 1000.0)),
+        ("disk_utilization", "percent", "Share of time the device was busy", |io| io.util_percentage),
+        ("disk_queue_depth", "", "Average number of requests queued or in flight", |io| io.queue_depth),
+    ];
+    for (name, unit, help, value) in rates {
+        m.gauge(name, unit, help);
+        for io in &info.disk_io_stats {
+            if let Some(value) = value(io) {
+                m.sample(&[("device", &io.device)], value);
+            }
+        }
+    }
+
+    // 系统
+    m.gauge("boot_time", "seconds", "System boot time since the Unix epoch").value(info.boot_time as f64);
+    m.gauge("uptime", "seconds", "System uptime").value(info.uptime as f64);
+    m.gauge("snapshot_timestamp", "seconds", "Time the snapshot was collected")
+        .value(info.timestamp.timestamp_millis() as f64 / 1000.0);
+
+    // 进程
+    m.gauge("processes", "", "Number of processes").value(info.process_count as f64);
+    m.gauge("threads", "", "Number of threads").value(info.thread_count as f64);
+    m.gauge("processes_running", "", "Number of running processes").value(info.running_process_count as f64);
+    m.gauge("processes_zombie", "", "Number of zombie processes").value(info.process_stats.zombie_count as f64);
+    m.gauge("processes_sleeping", "", "Number of sleeping processes").value(info.process_stats.sleeping_count as f64);
+    m.gauge("processes_blocked", "", "Number of blocked processes").value(info.process_stats.blocked_count as f64);
+    if let Some(states) = &info.process_stats.task_states {
+        m.gauge("processes_state", "", "Number of processes by Linux task state");
+        for (state, count) in [
+            ("R", states.running),
+            ("S", states.sleeping),
+            ("D", states.disk_sleep),
+            ("T", states.stopped),
+            ("t", states.tracing_stop),
+            ("Z", states.zombie),
+            ("I", states.idle),
+            ("X", states.dead),
+        ] {
+            m.sample(&[("state", state)], count as f64);
+        }
+    }
+    if let Some(running) = info.process_stats.procs_running {
+        m.gauge("procs_running", "", "Runnable tasks from /proc/stat").value(running as f64);
+    }
+    if let Some(blocked) = info.process_stats.procs_blocked {
+        m.gauge("procs_blocked", "", "Tasks blocked on I/O from /proc/stat").value(blocked as f64);
+    }
+    m.gauge("processes_cpu_usage", "percent", "Sum of CPU usage of all processes")
+        .value(info.process_stats.total_cpu_usage as f64);
+    m.gauge("processes_memory", "bytes", "Sum of resident memory of all processes")
+        .value(info.process_stats.total_memory_usage as f64);
+
+    // 温度
+    m.gauge("temperature", "celsius", "Component temperature");
+    for temp in &info.temperatures {
+        m.sample(&[("label", &temp.label)], temp.temp as f64);
+    }
+    m.gauge("temperature_warnings", "", "Number of active temperature warnings")
+        .value(info.temp_warnings.len() as f64);
+
+    // 网络
+    m.counter("network_received", "bytes", "Bytes received");
+    for net in &info.networks {
+        m.sample(&[("interface", &net.interface)], net.received_bytes as f64);
+    }
+    m.counter("network_transmitted", "bytes", "Bytes transmitted");
+    for net in &info.networks {
+        m.sample(&[("interface", &net.interface)], net.transmitted_bytes as f64);
+    }
+    m.counter("network_received_packets", "", "Packets received");
+    for net in &info.networks {
+        m.sample(&[("interface", &net.interface)], net.received_packets as f64);
+    }
+    m.counter("network_transmitted_packets", "", "Packets transmitted");
+    for net in &info.networks {
+        m.sample(&[("interface", &net.interface)], net.transmitted_packets as f64);
+    }
+    m.gauge("network_up", "", "Whether the interface operstate is up");
+    for net in &info.networks {
+        if let Some(link) = &net.link {
+            m.sample(&[("interface", &net.interface)], bool_value(link.operstate == "up"));
+        }
+    }
+    m.gauge("network_carrier", "", "Whether the interface has carrier");
+    for net in &info.networks {
+        if let Some(carrier) = net.link.as_ref().and_then(|link| link.carrier) {
+            m.sample(&[("interface", &net.interface)], bool_value(carrier));
+        }
+    }
+    m.gauge("network_mtu", "bytes", "Interface MTU");
+    for net in &info.networks {
+        if let Some(mtu) = net.link.as_ref().and_then(|link| link.mtu) {
+            m.sample(&[("interface", &net.interface)], mtu as f64);
+        }
+    }
+    m.gauge("network_speed", "bytes_per_second", "Negotiated link speed");
+    for net in &info.networks {
+        if let Some(speed) = net.link.as_ref().and_then(|link| link.speed) {
+            m.sample(&[("interface", &net.interface)], speed as f64 * 125_000.0);
+        }
+    }
+    m.gauge("network_info", "", "Interface link metadata, value is always 1");
+    for net in &info.networks {
+        if let Some(link) = &net.link {
+            m.sample(&[
+                ("interface", &net.interface),
+                ("operstate", &link.operstate),
+                ("duplex", link.duplex.as_deref().unwrap_or("")),
+                ("driver", link.driver.as_deref().unwrap_or("")),
+            ], 1.0);
+        }
+    }
+    m.counter("network_all_received", "bytes", "Bytes received on all interfaces").value(info.total_rx_bytes as f64);
+    m.counter("network_all_transmitted", "bytes", "Bytes transmitted on all interfaces").value(info.total_tx_bytes as f64);
+
+    let stats = &info.network_stats;
+    m.gauge("tcp_connections", "", "Number of TCP sockets").value(stats.tcp_connections as f64);
+    m.gauge("udp_connections", "", "Number of UDP sockets").value(stats.udp_connections as f64);
+    if let Some(states) = &stats.tcp_states {
+        m.gauge("tcp_sockets", "", "Number of TCP sockets by state");
+        for (state, count) in [
+            ("ESTABLISHED", states.established),
+            ("SYN_SENT", states.syn_sent),
+            ("SYN_RECV", states.syn_recv),
+            ("FIN_WAIT1", states.fin_wait1),
+            ("FIN_WAIT2", states.fin_wait2),
+            ("TIME_WAIT", states.time_wait),
+            ("CLOSE", states.close),
+            ("CLOSE_WAIT", states.close_wait),
+            ("LAST_ACK", states.last_ack),
+            ("LISTEN", states.listen),
+            ("CLOSING", states.closing),
+            ("NEW_SYN_RECV", states.new_syn_recv),
+        ] {
+            m.sample(&[("state", state)], count as f64);
+        }
+    }
+    if let Some(proto) = &stats.protocol_counters {
+        for (name, help, total, rate) in [
+            ("tcp_retransmitted_segments", "TCP segments retransmitted", proto.tcp_retrans_segs, proto.tcp_retrans_segs_sec),
+            ("tcp_resets_sent", "TCP resets sent", proto.tcp_out_rsts, proto.tcp_out_rsts_sec),
+            ("tcp_established_resets", "TCP connections reset from ESTABLISHED or CLOSE_WAIT", proto.tcp_estab_resets, proto.tcp_estab_resets_sec),
+            ("tcp_attempt_failures", "Failed TCP connection attempts", proto.tcp_attempt_fails, proto.tcp_attempt_fails_sec),
+            ("tcp_listen_overflows", "TCP accept queue overflows", proto.tcp_listen_overflows, proto.tcp_listen_overflows_sec),
+            ("tcp_listen_drops", "TCP SYNs dropped on listening sockets", proto.tcp_listen_drops, proto.tcp_listen_drops_sec),
+            ("udp_receive_errors", "UDP receive errors", proto.udp_in_errors, proto.udp_in_errors_sec),
+            ("udp_receive_buffer_errors", "UDP datagrams dropped because the receive buffer was full", proto.udp_rcvbuf_errors, proto.udp_rcvbuf_errors_sec),
+            ("udp_no_port", "UDP datagrams to ports without a listener", proto.udp_no_ports, proto.udp_no_ports_sec),
+        ] {
+            m.counter(name, "", help).value(total as f64);
+            if let Some(rate) = rate {
+                m.gauge(&format!("{}_rate", name), "", &format!("{} per second", help)).value(rate);
+            }
+        }
+        m.counter("tcp_output_segments", "", "TCP segments sent").value(proto.tcp_out_segs as f64);
+        if let Some(percentage) = proto.tcp_retrans_percentage {
+            m.gauge("tcp_retransmit_ratio", "percent", "Retransmitted share of sent TCP segments").value(percentage);
+        }
+        m.gauge("sockets_used", "", "Sockets in use").value(proto.sockets_used as f64);
+        m.gauge("tcp_sockets_inuse", "", "TCP sockets in use").value(proto.tcp_inuse as f64);
+        m.gauge("tcp_orphan_sockets", "", "Orphaned TCP sockets").value(proto.tcp_orphan as f64);
+        m.gauge("tcp_time_wait_sockets", "", "TCP sockets in TIME_WAIT").value(proto.tcp_time_wait as f64);
+        m.gauge("tcp_memory", "bytes", "Memory used by TCP socket buffers").value(proto.tcp_memory as f64);
+        m.gauge("udp_sockets_inuse", "", "UDP sockets in use").value(proto.udp_inuse as f64);
+        m.gauge("udp_memory", "bytes", "Memory used by UDP socket buffers").value(proto.udp_memory as f64);
+    }
+    // listeners 已在采集时去重，不会产生重复序列
+    m.gauge("listen_port", "", "Listening socket, value is always 1");
+    for listener in &stats.listeners {
+        m.sample(&[
+            ("protocol", &listener.protocol),
+            ("address", &listener.address),
+            ("port", &listener.port.to_string()),
+        ], 1.0);
+    }
+    m.counter("network_receive_errors", "", "Receive errors");
+    for iface in &stats.interface_stats {
+        m.sample(&[("interface", &iface.name)], iface.rx_errors as f64);
+    }
+    m.counter("network_transmit_errors", "", "Transmit errors");
+    for iface in &stats.interface_stats {
+        m.sample(&[("interface", &iface.name)], iface.tx_errors as f64);
+    }
+    m.counter("network_receive_dropped", "", "Received packets dropped");
+    for iface in &stats.interface_stats {
+        m.sample(&[("interface", &iface.name)], iface.rx_dropped as f64);
+    }
+    m.counter("network_transmit_dropped", "", "Transmitted packets dropped");
+    for iface in &stats.interface_stats {
+        m.sample(&[("interface", &iface.name)], iface.tx_dropped as f64);
+    }
+    m.gauge("network_receive_rate", "bytes_per_second", "Receive throughput");
+    for iface in &stats.interface_stats {
+        if let Some(rate) = iface.rx_bytes_sec {
+            m.sample(&[("interface", &iface.name)], rate);
+        }
+    }
+    m.gauge("network_transmit_rate", "bytes_per_second", "Transmit throughput");
+    for iface in &stats.interface_stats {
+        if let Some(rate) = iface.tx_bytes_sec {
+            m.sample(&[("interface", &iface.name)], rate);
+        }
+    }
+    m.gauge("network_receive_packet_rate", "packets_per_second", "Receive packet rate");
+    for iface in &stats.interface_stats {
+        if let Some(rate) = iface.rx_packets_sec {
+            m.sample(&[("interface", &iface.name)], rate);
+        }
+    }
+    m.gauge("network_transmit_packet_rate", "packets_per_second", "Transmit packet rate");
+    for iface in &stats.interface_stats {
+        if let Some(rate) = iface.tx_packets_sec {
+            m.sample(&[("interface", &iface.name)], rate);
+        }
+    }
+
+    // 电源
+    let power = &info.power_info;
+    m.gauge("ac_powered", "", "Whether the system runs on AC power").value(bool_value(power.ac_powered));
+    m.gauge("battery_present", "", "Whether a battery is present").value(bool_value(power.battery_present));
+    if let Some(percentage) = power.battery_percentage {
+        m.gauge("battery_charge", "percent", "Battery charge level").value(percentage as f64);
+    }
+    if let Some(remaining) = power.battery_time_remaining {
+        m.gauge("battery_time_remaining", "seconds", "Estimated battery time remaining").value(remaining as f64);
+    }
+    if let Some(consumption) = power.power_consumption {
+        m.gauge("power_consumption", "watts", "System power consumption").value(consumption as f64);
+    }
+
+    // 性能指标
+    let perf = &info.performance_metrics;
+    m.gauge("cpu_time", "percent", "Share of CPU time by mode");
+    for (mode, value) in [
+        ("user", perf.user_percentage),
+        ("nice", perf.nice_percentage),
+        ("system", perf.system_percentage),
+        ("idle", perf.idle_percentage),
+        ("iowait", perf.iowait_percentage),
+        ("irq", perf.irq_percentage),
+        ("softirq", perf.softirq_percentage),
+        ("steal", perf.steal_percentage),
+        ("guest", perf.guest_percentage),
+    ] {
+        if let Some(value) = value {
+            m.sample(&[("mode", mode)], value as f64);
+        }
+    }
+    m.gauge("cpu_core_time", "percent", "Share of CPU time by core and mode");
+    for core in &perf.per_core {
+        let cpu = core.cpu.to_string();
+        for (mode, value) in [
+            ("user", core.user_percentage),
+            ("nice", core.nice_percentage),
+            ("system", core.system_percentage),
+            ("idle", core.idle_percentage),
+            ("iowait", core.iowait_percentage),
+            ("irq", core.irq_percentage),
+            ("softirq", core.softirq_percentage),
+            ("steal", core.steal_percentage),
+            ("guest", core.guest_percentage),
+        ] {
+            if let Some(value) = value {
+                m.sample(&[("cpu", &cpu), ("mode", mode)], value as f64);
+            }
+        }
+    }
+    m.gauge("cpu_queue_length", "", "Run queue length").value(perf.cpu_queue_length as f64);
+    m.counter("context_switches", "", "Context switches since boot").value(perf.context_switches as f64);
+    m.counter("interrupts", "", "Interrupts since boot").value(perf.interrupts as f64);
+    if let Some(rate) = perf.context_switches_per_sec {
+        m.gauge("context_switch_rate", "", "Context switches per second").value(rate);
+    }
+    if let Some(rate) = perf.interrupts_per_sec {
+        m.gauge("interrupt_rate", "", "Interrupts per second").value(rate);
+    }
+    m.gauge("cpu_core_context_switch_rate", "", "Schedule calls per second by core");
+    for core in &perf.per_core {
+        if let Some(rate) = core.context_switches_per_sec {
+            m.sample(&[("cpu", &core.cpu.to_string())], rate);
+        }
+    }
+    m.gauge("cpu_core_interrupt_rate", "", "Interrupts per second by core");
+    for core in &perf.per_core {
+        if let Some(rate) = core.interrupts_per_sec {
+            m.sample(&[("cpu", &core.cpu.to_string())], rate);
+        }
+    }
+
+    // 压力停滞信息（PSI）
+    let pressure = &perf.pressure;
+    m.gauge("pressure_available", "", "Whether the kernel exposes pressure stall information").value(bool_value(pressure.available));
+    let mut stalls: Vec<(&str, &str, &PressureStall)> = Vec::new();
+    for (resource, value) in [("cpu", &pressure.cpu), ("memory", &pressure.memory), ("io", &pressure.io)] {
+        if let Some(value) = value {
+            stalls.push((resource, "some", &value.some));
+            if let Some(full) = &value.full {
+                stalls.push((resource, "full", full));
+            }
+        }
+    }
+    if !stalls.is_empty() {
+        m.gauge("pressure_stall", "percent", "Share of time tasks were stalled on a resource, averaged over a window");
+        for (resource, kind, stall) in &stalls {
+            for (window, value) in [("10s", stall.avg10), ("60s", stall.avg60), ("300s", stall.avg300)] {
+                m.sample(&[("resource", resource), ("kind", kind), ("window", window)], value);
+            }
+        }
+        m.counter("pressure_stalled", "seconds", "Total time tasks were stalled on a resource");
+        for (resource, kind, stall) in &stalls {
+            m.sample(&[("resource", resource), ("kind", kind)], stall.total as f64 / 1_000_000.0);
+        }
+    }
+
+    m.finish()
+}
@@ -0,0 +1,486 @@
+use crate::{CpuInfo, DisksInfo, HealthStatus, MemoryInfo, NetworkOverview, PowerInfo, ProcessSummary, SensorsInfo, SystemInfo, TempInfo};
+use crate::{DiskInfo, DiskIoStats, GpuInfo, InterfaceStats, LinkInfo, Listener, LoadAverage, NetworkInfo, NetworkStats, PerformanceMetrics, ProcessStats, TempStatus};
+use crate::{Connection, CoreTimes, MemoryDetails, PressureInfo, PressureStall, ResourcePressure, MountIo, ProtocolCounters, TcpStates, ProcessCgroup, ProcessDetail, ProcessIo, ResourceLimit, TaskStates};
+use crate::processes::{ProcessEntry, ProcessList, ProcessNode, ProcessTree, SubtreeTotals};
+use crate::inventory::BlockDeviceInventory;
+use crate::v2::SystemInfoV2;
+use chrono::{DateTime, Utc};
+use schemars::generate::{SchemaGenerator, SchemaSettings};
+use schemars::Schema;
+use serde_json::{json, Value};
+use std::sync::OnceLock;
+
+// 温度历史记录的响应类型
+type TempHistory = Vec<(DateTime<Utc>, Vec<TempInfo>)>;
+type PressureHistory = Vec<(DateTime<Utc>, PressureInfo)>;
+
+// 网络连接列表的响应类型
+type ConnectionList = Vec<Connection>;
+
+// 块设备清单的响应类型
+type BlockInventory = Vec<BlockDeviceInventory>;
+
+// 所有响应类型：生成 OpenAPI components 和单独的 JSON Schema
+macro_rules! response_types {
+    ($($ty:ident),* $(,)?) => {
+        pub const SCHEMA_NAMES: &[&str] = &[$(stringify!($ty)),*];
+
+        fn root_schema(name: &str) -> Option<Schema> {
+            let generator = SchemaSettings::draft2020_12().for_serialize().into_generator();
+            match name {
+                $(stringify!($ty) => Some(generator.into_root_schema_for::<$ty>()),)*
+                _ => None,
+            }
+        }
+
+        fn register_components(generator: &mut SchemaGenerator) -> Vec<(&'static str, Schema)> {
+            vec![$((stringify!($ty), generator.subschema_for::<$ty>()),)*]
+        }
+
+        // 各响应类型的默认值，供 schema 漂移测试使用
+        #[cfg(test)]
+        fn response_default(name: &str) -> Option<Value> {
+            match name {
+                $(stringify!($ty) => serde_json::to_value(<$ty>::default()).ok(),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+response_types!(
+    SystemInfo, CpuInfo, MemoryInfo, DisksInfo, NetworkOverview, SensorsInfo, ProcessSummary,
+    PowerInfo, HealthStatus, TempHistory, DiskInfo, DiskIoStats, GpuInfo, InterfaceStats,
+    LoadAverage, NetworkInfo, NetworkStats, PerformanceMetrics, ProcessStats, TempInfo, TempStatus,
+    SystemInfoV2, ProcessList, ProcessEntry, ProcessDetail, ProcessIo, ProcessCgroup, ResourceLimit,
+    ProcessTree, ProcessNode, SubtreeTotals, TaskStates, CoreTimes, LinkInfo, Listener,
+    ConnectionList, Connection, TcpStates, ProtocolCounters,
+    MountIo, BlockInventory, BlockDeviceInventory, MemoryDetails,
+    PressureInfo, ResourcePressure, PressureStall, PressureHistory,
+);
+
+// 单个响应类型的 JSON Schema（draft 2020-12）
+pub fn json_schema(name: &str) -> Option<Value> {
+    root_schema(name).map(Schema::to_value)
+}
+
+pub fn document() -> &'static Value {
+    static DOCUMENT: OnceLock<Value> = OnceLock::new();
+    DOCUMENT.get_or_init(build_document)
+}
+
+fn json_response(description: &str, schema: &str) -> Value {
+    json!({
+        "description": description,
+        "content": {
+            "application/json": {
+                "schema": { "$ref": format!("#/components/schemas/{}", schema) }
+            }
+        }
+    })
+}
+
+fn get(summary: &str, response: Value) -> Value {
+    json!({ "get": { "summary": summary, "responses": { "200": response } } })
+}
+
+fn build_document() -> Value {
+    let mut generator = SchemaSettings::openapi3().for_serialize().into_generator();
+    let subschemas = register_components(&mut generator);
+    let mut schemas = generator.take_definitions(true);
+    // 数组等类型不会生成定义，直接以内联 schema 注册为组件
+    for (name, mut schema) in subschemas {
+        if !schemas.contains_key(name) {
+            for transform in generator.transforms_mut() {
+                transform.transform(&mut schema);
+            }
+            schemas.insert(name.to_string(), schema.to_value());
+        }
+    }
+
+    let projection_params = json!([
+        {
+            "name": "fields",
+            "in": "query",
+            "description": "Comma separated fields to keep, nested fields use dots, e.g. cpu_usage,disks.mount_point",
+            "schema": { "type": "string" }
+        },
+        {
+            "name": "exclude",
+            "in": "query",
+            "description": "Comma separated fields to remove, same syntax as fields",
+            "schema": { "type": "string" }
+        }
+    ]);
+
+    let mut system = get("Full system snapshot", json_response("System snapshot, pruned by the projection parameters", "SystemInfo"));
+    system["get"]["parameters"] = projection_params.clone();
+
+    let mut stream_params = projection_params.as_array().cloned().unwrap_or_default();
+    stream_params.push(json!({
+        "name": "min_interval_ms",
+        "in": "query",
+        "description": "Minimum interval between two events",
+        "schema": { "type": "integer", "minimum": 0 }
+    }));
+    stream_params.push(json!({
+        "name": "Last-Event-ID",
+        "in": "header",
+        "description": "Resume after the given event id",
+        "schema": { "type": "integer", "minimum": 0 }
+    }));
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Hardware Monitor API",
+            "description": env!("CARGO_PKG_DESCRIPTION"),
+            "version": env!("CARGO_PKG_VERSION")
+        },
+        "paths": {
+            "/api/system": system,
+            "/api/v2/system": get("Full system snapshot, nested by subsystem with units in field names; values that were not collected are null", json_response("System snapshot (v2)", "SystemInfoV2")),
+            "/api/cpu": get("CPU usage, frequency, temperature and load", json_response("CPU information", "CpuInfo")),
+            "/api/memory": get("Memory and swap", json_response("Memory information", "MemoryInfo")),
+            "/api/disks": {
+                "get": {
+                    "summary": "Filesystems and disk I/O",
+                    "parameters": [
+                        { "name": "partitions", "in": "query", "description": "Include partitions in disk_io_stats, defaults to true", "schema": { "type": "boolean" } }
+                    ],
+                    "responses": { "200": json_response("Disk information", "DisksInfo") }
+                }
+            },
+            "/api/network": get("Interfaces and connections", json_response("Network information", "NetworkOverview")),
+            "/api/network/connections": {
+                "get": {
+                    "summary": "TCP/UDP sockets with owning process (Linux only)",
+                    "parameters": [
+                        { "name": "state", "in": "query", "description": "Socket state, e.g. LISTEN, ESTABLISHED, TIME_WAIT, UNCONN; case-insensitive", "schema": { "type": "string" } },
+                        { "name": "port", "in": "query", "description": "Match either the local or the remote port", "schema": { "type": "integer", "minimum": 0, "maximum": 65535 } },
+                        { "name": "protocol", "in": "query", "schema": { "type": "string", "enum": ["tcp", "udp"] } }
+                    ],
+                    "responses": {
+                        "200": json_response("Matching sockets", "ConnectionList"),
+                        "501": { "description": "Not supported on this platform" }
+                    }
+                }
+            },
+            "/api/sensors": get("Component temperatures", json_response("Sensor readings", "SensorsInfo")),
+            "/api/power": get("Power state", json_response("Power information", "PowerInfo")),
+            "/api/processes/summary": get("Process counts", json_response("Process summary", "ProcessSummary")),
+            "/api/processes": {
+                "get": {
+                    "summary": "Process list with sorting, filtering and pagination",
+                    "parameters": [
+                        { "name": "sort", "in": "query", "description": "Sort key, cpu/mem/io sort descending", "schema": { "type": "string", "enum": ["pid", "cpu", "mem", "io"] } },
+                        { "name": "limit", "in": "query", "schema": { "type": "integer", "minimum": 0 } },
+                        { "name": "offset", "in": "query", "schema": { "type": "integer", "minimum": 0 } },
+                        { "name": "name", "in": "query", "description": "Regular expression matched against the process name", "schema": { "type": "string" } },
+                        { "name": "user", "in": "query", "description": "User name or uid", "schema": { "type": "string" } },
+                        { "name": "state", "in": "query", "description": "Process state, e.g. Runnable, Sleeping, Zombie", "schema": { "type": "string" } }
+                    ],
+                    "responses": {
+                        "200": json_response("Matching processes", "ProcessList"),
+                        "400": { "description": "Invalid name pattern" }
+                    }
+                }
+            },
+            "/api/processes/tree": {
+                "get": {
+                    "summary": "Process tree with per-subtree CPU, memory and thread totals",
+                    "parameters": [
+                        { "name": "pid", "in": "query", "description": "Root the tree at this pid", "schema": { "type": "integer", "minimum": 0 } },
+                        { "name": "name", "in": "query", "description": "Root the tree at every process with this exact name", "schema": { "type": "string" } }
+                    ],
+                    "responses": {
+                        "200": json_response("Process tree", "ProcessTree"),
+                        "404": { "description": "No matching root process" }
+                    }
+                }
+            },
+            "/api/processes/{pid}": {
+                "get": {
+                    "summary": "Process details from procfs (Linux only)",
+                    "parameters": [
+                        { "name": "pid", "in": "path", "required": true, "schema": { "type": "integer", "minimum": 0 } },
+                        { "name": "show_env", "in": "query", "description": "Include environment variable values, redacted by default; only allowed when the server sets PROCESS_SHOW_ENV=true", "schema": { "type": "boolean" } }
+                    ],
+                    "responses": {
+                        "200": json_response("Process details, fields that could not be read are null", "ProcessDetail"),
+                        "403": { "description": "show_env requested but not enabled on the server" },
+                        "404": { "description": "No such process" },
+                        "501": { "description": "Not supported on this platform" }
+                    }
+                }
+            },
+            "/api/inventory/block": {
+                "get": {
+                    "summary": "Physical block devices from sysfs (Linux only)",
+                    "responses": {
+                        "200": json_response("Model, serial, size, sector sizes, scheduler, queue and transport of each disk", "BlockInventory"),
+                        "501": { "description": "Not supported on this platform" }
+                    }
+                }
+            },
+            "/api/temperature/history": get("Recent temperature samples", json_response("Temperature history as [timestamp, readings] pairs", "TempHistory")),
+            "/api/pressure/history": get("Recent pressure stall samples (Linux only), empty when the kernel has no PSI", json_response("Pressure history as [timestamp, pressure] pairs", "PressureHistory")),
+            "/api/health": get("Health check", json_response("Service status", "HealthStatus")),
+            "/api/stream": {
+                "get": {
+                    "summary": "Server-Sent Events stream of snapshots",
+                    "parameters": stream_params,
+                    "responses": {
+                        "200": {
+                            "description": "Each `snapshot` event carries a SystemInfo payload, pruned by the projection parameters",
+                            "content": { "text/event-stream": { "schema": { "type": "string" } } }
+                        }
+                    }
+                }
+            },
+            "/api/ws": {
+                "get": {
+                    "summary": "WebSocket topic subscriptions",
+                    "responses": { "101": { "description": "Switching protocols" } }
+                }
+            },
+            "/metrics": {
+                "get": {
+                    "summary": "Prometheus metrics",
+                    "responses": {
+                        "200": {
+                            "description": "Prometheus text format, or OpenMetrics when requested via Accept",
+                            "content": { "text/plain": { "schema": { "type": "string" } } }
+                        }
+                    }
+                }
+            },
+            "/api/openapi.json": {
+                "get": {
+                    "summary": "This document",
+                    "responses": { "200": { "description": "OpenAPI document" } }
+                }
+            },
+            "/api/schemas/{name}": {
+                "get": {
+                    "summary": "JSON Schema of a response type",
+                    "parameters": [{
+                        "name": "name",
+                        "in": "path",
+                        "required": true,
+                        "schema": { "type": "string", "enum": SCHEMA_NAMES }
+                    }],
+                    "responses": {
+                        "200": { "description": "JSON Schema (draft 2020-12)" },
+                        "404": { "description": "Unknown type" }
+                    }
+                }
+            }
+        },
+        "components": { "schemas": schemas }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::{BlockDeviceV2, CoreTimeV2, CpuTimeV2, CpuV2, FilesystemIoV2, FilesystemV2, GpuV2, HostV2, InterfaceV2, LinkV2};
+    use crate::v2::{LoadAverageV2, MemoryDetailsV2, MemoryV2, NetworkV2, PowerV2, PressureStallV2, PressureV2, ProcessesV2, ProtocolV2};
+    use crate::v2::{ResourcePressureV2, SensorsV2, StorageV2, SwapV2, TemperatureV2};
+    use serde_json::Map;
+    use std::collections::BTreeSet;
+
+    // 只出现在其他响应类型内部的定义
+    macro_rules! nested_types {
+        ($($ty:ident),* $(,)?) => {
+            fn nested_default(name: &str) -> Option<Value> {
+                match name {
+                    $(stringify!($ty) => serde_json::to_value(<$ty>::default()).ok(),)*
+                    _ => None,
+                }
+            }
+        };
+    }
+
+    nested_types!(
+        HostV2, CpuV2, LoadAverageV2, CpuTimeV2, CoreTimeV2, MemoryV2, MemoryDetailsV2, SwapV2, GpuV2,
+        StorageV2, FilesystemV2, FilesystemIoV2, BlockDeviceV2, NetworkV2, ProtocolV2, InterfaceV2, LinkV2,
+        ProcessesV2, SensorsV2, TemperatureV2, PowerV2, PressureV2, ResourcePressureV2, PressureStallV2,
+    );
+
+    fn default_sample(name: &str) -> Option<Value> {
+        response_default(name).or_else(|| nested_default(name))
+    }
+
+    fn variants(schema: &Value) -> Option<&Vec<Value>> {
+        schema.get("anyOf").or_else(|| schema.get("oneOf")).and_then(Value::as_array)
+    }
+
+    fn json_type(value: &Value) -> &'static str {
+        match value {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(n) if n.is_f64() => "number",
+            Value::Number(_) => "integer",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        }
+    }
+
+    // 用默认值检查 schema：对象字段集合必须一致，值的类型和枚举值必须符合 schema。
+    // null、空数组和空映射无法展开，其中引用的定义改用该类型自身的默认值检查
+    struct Checker<'a> {
+        // 根类型的 schema 也登记在其中，供递归类型的 "#" 引用
+        defs: &'a Map<String, Value>,
+        root: &'a str,
+        pending: Vec<String>,
+    }
+
+    impl Checker<'_> {
+        fn ref_name(&self, schema: &Value) -> Option<String> {
+            match schema.get("$ref").and_then(Value::as_str)? {
+                "#" => Some(self.root.to_string()),
+                reference => reference.rsplit('/').next().map(str::to_string),
+            }
+        }
+
+        // 记录没有值可供检查的 schema 中引用的定义
+        fn expect(&mut self, schema: &Value) {
+            if let Some(name) = self.ref_name(schema) {
+                self.pending.push(name);
+            }
+            for child in variants(schema).into_iter().flatten()
+                .chain(schema.get("prefixItems").and_then(Value::as_array).into_iter().flatten())
+                .chain(schema.get("items"))
+                .chain(schema.get("additionalProperties").filter(|v| v.is_object()))
+            {
+                self.expect(child);
+            }
+        }
+
+        fn walk(&mut self, value: &Value, schema: &Value, path: &str) {
+            let defs = self.defs;
+            if let Some(name) = self.ref_name(schema) {
+                let def = defs.get(&name).unwrap_or_else(|| panic!("{}: dangling reference {}", path, name));
+                self.pending.push(name);
+                return self.walk(value, def, path);
+            }
+            if let Some(variants) = variants(schema) {
+                let is_null = |v: &Value| v.get("type") == Some(&json!("null"));
+                if value.is_null() {
+                    assert!(variants.iter().any(is_null), "{}: null for non-nullable schema", path);
+                    return self.expect(schema);
+                }
+                let variant = variants.iter()
+                    .find(|v| !is_null(v))
+                    .unwrap_or_else(|| panic!("{}: no non-null variant", path));
+                return self.walk(value, variant, path);
+            }
+
+            let actual = json_type(value);
+            let allowed: Vec<&str> = match schema.get("type") {
+                Some(Value::String(t)) => vec![t.as_str()],
+                Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+                _ => panic!("{}: schema has no type", path),
+            };
+            assert!(
+                allowed.contains(&actual) || (actual == "integer" && allowed.contains(&"number")),
+                "{}: {} where schema expects {:?}", path, actual, allowed,
+            );
+
+            match value {
+                Value::Object(map) if schema.get("properties").is_none() => {
+                    // BTreeMap 等映射类型：逐个检查值
+                    let values = schema.get("additionalProperties")
+                        .unwrap_or_else(|| panic!("{}: schema has no properties", path));
+                    if map.is_empty() {
+                        self.expect(values);
+                    }
+                    for (key, child) in map {
+                        self.walk(child, values, &format!("{}.{}", path, key));
+                    }
+                }
+                Value::Object(map) => {
+                    let properties = schema["properties"].as_object()
+                        .unwrap_or_else(|| panic!("{}: schema has no properties", path));
+                    let mut value_keys: Vec<_> = map.keys().collect();
+                    let mut schema_keys: Vec<_> = properties.keys().collect();
+                    value_keys.sort();
+                    schema_keys.sort();
+                    assert_eq!(value_keys, schema_keys, "{}: fields drifted from schema", path);
+                    for (key, child) in map {
+                        self.walk(child, &properties[key], &format!("{}.{}", path, key));
+                    }
+                }
+                Value::Array(items) if items.is_empty() => self.expect(schema),
+                Value::Array(items) => {
+                    for (i, item) in items.iter().enumerate() {
+                        let item_schema = match &schema["prefixItems"] {
+                            Value::Array(prefix) => &prefix[i],
+                            _ => &schema["items"],
+                        };
+                        self.walk(item, item_schema, &format!("{}[{}]", path, i));
+                    }
+                }
+                Value::String(s) => {
+                    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+                        assert!(allowed.contains(&json!(s)), "{}: {} not in schema enum", path, s);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // 从响应类型的默认值出发，直到 schema 中引用的每个定义都用对应类型的默认值检查过；
+    // 缺少默认值的定义（例如新增的嵌套类型）会让测试失败
+    fn check(name: &str) {
+        let schema = json_schema(name).expect("schema registered");
+        let mut defs = schema.get("$defs").and_then(Value::as_object).cloned().unwrap_or_default();
+        defs.entry(name).or_insert_with(|| schema.clone());
+        let mut checker = Checker { defs: &defs, root: name, pending: Vec::new() };
+        let sample = default_sample(name).unwrap_or_else(|| panic!("no default for {}", name));
+        checker.walk(&sample, &schema, name);
+
+        let mut checked = BTreeSet::new();
+        while let Some(def) = checker.pending.pop() {
+            if !checked.insert(def.clone()) {
+                continue;
+            }
+            let sample = default_sample(&def).unwrap_or_else(|| panic!("{}: no default to check {}", name, def));
+            checker.walk(&sample, &defs[&def], &def);
+        }
+    }
+
+    #[test]
+    fn response_types_match_schemas() {
+        for name in SCHEMA_NAMES {
+            check(name);
+        }
+    }
+
+    #[test]
+    fn openapi_references_resolve() {
+        fn walk(value: &Value, schemas: &Map<String, Value>) {
+            match value {
+                Value::Object(map) => {
+                    if let Some(reference) = map.get("$ref").and_then(Value::as_str) {
+                        let name = reference.trim_start_matches("#/components/schemas/");
+                        assert!(schemas.contains_key(name), "dangling reference {}", reference);
+                    }
+                    map.values().for_each(|v| walk(v, schemas));
+                }
+                Value::Array(items) => items.iter().for_each(|v| walk(v, schemas)),
+                _ => {}
+            }
+        }
+
+        let doc = document();
+        let schemas = doc["components"]["schemas"].as_object().unwrap();
+        for name in SCHEMA_NAMES {
+            assert!(schemas.contains_key(*name), "{} missing from components", name);
+        }
+        walk(doc, schemas);
+    }
+}
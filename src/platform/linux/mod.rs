@@ -1,6 +1,30 @@
-use crate::{GpuInfo, DiskIoStats, NetworkStats, ProcessStats, PowerInfo, PerformanceMetrics, InterfaceStats};
-use sysinfo::{System, SystemExt, ProcessExt, CpuExt, NetworksExt};
-use procfs::net::TcpState;
+use crate::{GpuInfo, DiskIoStats, NetworkStats, ProcessStats, PowerInfo, PerformanceMetrics, InterfaceStats, Listener, Connection};
+use crate::{TcpStates, ProtocolCounters, DiskInfo, MemoryDetails};
+use crate::{ProcessDetail, ProcessIo, ProcessCgroup, ResourceLimit, TaskStates, CoreTimes, LinkInfo};
+use crate::{PressureInfo, ResourcePressure, PressureStall};
+use super::{BlockNode, TaskScan};
+use sysinfo::{System, SystemExt, ProcessExt, ProcessStatus};
+use procfs::net::{DeviceStatus, TcpNetEntry, TcpState, UdpNetEntry, UdpState};
+use procfs::DiskStat;
+use procfs::process::{FDTarget, Limit, LimitValue, Process};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ffi::{CStr, CString};
+use std::mem::MaybeUninit;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::Path;
+use std::time::Instant;
+
+pub const CAPABILITIES: super::Capabilities = super::Capabilities {
+    disk_io: true,
+    connections: true,
+    interface_counters: true,
+    cpu_time: true,
+    load_average: true,
+    thread_count: true,
+    power: false,
+    process_detail: true,
+    block_inventory: true,
+};
 
 #[cfg(feature = "nvml")]
 pub fn get_gpu_info() -> Option<GpuInfo> {
@@ -33,89 +57,788 @@ pub fn get_gpu_info() -> Option<GpuInfo> {
     None
 }
 
-pub fn get_disk_io_stats() -> Vec<DiskIoStats> {
-    if let Ok(disks) = procfs::diskstats() {
-        disks.iter().map(|disk| {
-            DiskIoStats {
-                device: disk.name.clone(),
-                reads: disk.reads,
-                writes: disk.writes,
-                read_bytes: disk.sectors_read * 512,
-                write_bytes: disk.sectors_written * 512,
-                read_time: disk.time_reading,
-                write_time: disk.time_writing,
-                io_in_progress: disk.time_in_progress,
+// /proc/meminfo 每行为 "Dirty:  136660 kB"，带 kB 后缀的值换算为字节，HugePages_* 为页数
+fn read_meminfo() -> Option<HashMap<String, u64>> {
+    let content = std::fs::read_to_string("/proc/meminfo").ok()?;
+    Some(content.lines().filter_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        let mut fields = value.split_whitespace();
+        let number: u64 = fields.next()?.parse().ok()?;
+        let number = if fields.next() == Some("kB") { number * 1024 } else { number };
+        Some((key.to_string(), number))
+    }).collect())
+}
+
+// /proc/vmstat 每行为 "pgfault 47816087"
+fn read_vmstat() -> HashMap<String, u64> {
+    let Ok(content) = std::fs::read_to_string("/proc/vmstat") else {
+        return HashMap::new();
+    };
+    content.lines().filter_map(|line| {
+        let (key, value) = line.split_once(' ')?;
+        Some((key.to_string(), value.trim().parse().ok()?))
+    }).collect()
+}
+
+pub fn get_memory_details(counters: &mut Counters) -> Option<MemoryDetails> {
+    let meminfo = read_meminfo()?;
+    let values = read_vmstat();
+
+    let now = Instant::now();
+    let previous = counters.vmstat.take();
+    let memory = |key: &str| meminfo.get(key).copied().unwrap_or(0);
+    let current = |key: &str| values.get(key).copied().unwrap_or(0);
+    let per_sec = |key: &str| {
+        let (at, prev) = previous.as_ref()?;
+        let secs = now.duration_since(*at).as_secs_f64();
+        let (current, previous) = (values.get(key)?, prev.get(key)?);
+        let delta = counter_delta(*current, *previous)?;
+        (secs > 0.0).then(|| delta as f64 / secs)
+    };
+
+    let details = MemoryDetails {
+        buffers: memory("Buffers"),
+        cached: memory("Cached"),
+        shmem: memory("Shmem"),
+        slab: memory("Slab"),
+        slab_reclaimable: memory("SReclaimable"),
+        slab_unreclaimable: memory("SUnreclaim"),
+        dirty: memory("Dirty"),
+        writeback: memory("Writeback"),
+        mapped: memory("Mapped"),
+        anon: memory("AnonPages"),
+        committed_as: memory("Committed_AS"),
+        commit_limit: memory("CommitLimit"),
+        hugepages_total: memory("HugePages_Total"),
+        hugepages_free: memory("HugePages_Free"),
+        hugepages_reserved: memory("HugePages_Rsvd"),
+        hugepages_surplus: memory("HugePages_Surp"),
+        hugepage_size: memory("Hugepagesize"),
+        anon_hugepages: memory("AnonHugePages"),
+        page_faults: current("pgfault"),
+        major_faults: current("pgmajfault"),
+        swap_in: current("pswpin"),
+        swap_out: current("pswpout"),
+        oom_kills: values.get("oom_kill").copied(),
+        page_faults_sec: per_sec("pgfault"),
+        major_faults_sec: per_sec("pgmajfault"),
+        swap_in_sec: per_sec("pswpin"),
+        swap_out_sec: per_sec("pswpout"),
+        oom_kills_sec: per_sec("oom_kill"),
+    };
+
+    counters.vmstat = Some((now, values));
+    Some(details)
+}
+
+// 始终跳过的伪文件系统
+const PSEUDO_FS_TYPES: &[&str] = &[
+    "rootfs", "proc", "sysfs", "devpts", "cgroup", "cgroup2", "pstore", "securityfs", "debugfs",
+    "tracefs", "configfs", "fusectl", "mqueue", "hugetlbfs", "bpf", "binfmt_misc", "autofs",
+    "rpc_pipefs", "nsfs", "efivarfs", "selinuxfs",
+];
+
+// 网络和 FUSE 文件系统在服务端无响应时 statvfs 会长时间阻塞，而采集期间持有 sys 锁，同样跳过
+const REMOTE_FS_TYPES: &[&str] = &[
+    "nfs", "nfs4", "cifs", "smb3", "smbfs", "ceph", "glusterfs", "9p", "afs", "lustre", "gfs2", "ocfs2",
+];
+
+fn skips_statvfs(fs_type: &str) -> bool {
+    PSEUDO_FS_TYPES.contains(&fs_type)
+        || REMOTE_FS_TYPES.contains(&fs_type)
+        || fs_type == "fuse"
+        || fs_type.starts_with("fuse.")
+}
+
+// 挂载表中的空白和反斜杠以八进制转义
+fn unescape_mount_field(field: &str) -> String {
+    field.replace("\\040", " ")
+        .replace("\\011", "\t")
+        .replace("\\012", "\n")
+        .replace("\\134", "\\")
+}
+
+fn statvfs(path: &str) -> Option<libc::statvfs> {
+    let path = CString::new(path).ok()?;
+    let mut stat = MaybeUninit::<libc::statvfs>::uninit();
+    if unsafe { libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    Some(unsafe { stat.assume_init() })
+}
+
+// 从 /proc/self/mountinfo 读取挂载点及其块设备，容量和 inode 来自 statvfs；使用率由调用方计算
+// statvfs 的字段在 32 位目标上是 u32，需要保留转换
+#[allow(clippy::unnecessary_cast)]
+pub fn get_filesystems() -> Option<Vec<DiskInfo>> {
+    let content = std::fs::read_to_string("/proc/self/mountinfo").ok()?;
+    let mut filesystems: Vec<DiskInfo> = Vec::new();
+
+    for line in content.lines() {
+        // 格式为 "id 父id 主:次 根 挂载点 挂载选项 [可选字段...] - 类型 来源 超级块选项"
+        let Some((mount, superblock)) = line.split_once(" - ") else {
+            continue;
+        };
+        let mount: Vec<&str> = mount.split_whitespace().collect();
+        let superblock: Vec<&str> = superblock.split_whitespace().collect();
+        let ([_, _, majmin, _, mount_point, options, ..], [fs_type, source, super_options, ..]) = (&mount[..], &superblock[..]) else {
+            continue;
+        };
+        if skips_statvfs(fs_type) {
+            continue;
+        }
+        let mount_point = unescape_mount_field(mount_point);
+        let Some(stat) = statvfs(&mount_point) else {
+            continue;
+        };
+
+        // 与 /proc/self/mounts 一致：挂载点选项在前，再追加超级块选项
+        let mut mount_options: Vec<String> = options.split(',').map(str::to_string).collect();
+        for option in super_options.split(',') {
+            if !mount_options.iter().any(|o| o == option) {
+                mount_options.push(option.to_string());
             }
-        }).collect()
-    } else {
-        Vec::new()
+        }
+        let source = unescape_mount_field(source);
+        let fragment_size = stat.f_frsize as u64;
+        let inodes_total = (stat.f_files > 0).then_some(stat.f_files as u64);
+        let filesystem = DiskInfo {
+            block_device: block_device_name(majmin).or_else(|| source_device_name(&source)),
+            name: source,
+            mount_point,
+            file_system: fs_type.to_string(),
+            read_only: Some(mount_options.iter().any(|option| option == "ro")),
+            mount_options,
+            total_space: stat.f_blocks as u64 * fragment_size,
+            available_space: stat.f_bavail as u64 * fragment_size,
+            inodes_total,
+            inodes_used: inodes_total.map(|total| total.saturating_sub(stat.f_ffree as u64)),
+            inodes_free: inodes_total.map(|_| stat.f_ffree as u64),
+            ..DiskInfo::default()
+        };
+        // 同一挂载点重复挂载时，后挂载的会遮住先前的
+        filesystems.retain(|fs| fs.mount_point != filesystem.mount_point);
+        filesystems.push(filesystem);
     }
+    Some(filesystems)
 }
 
-pub fn get_network_stats(sys: &System) -> NetworkStats {
-    let tcp = procfs::net::tcp().unwrap_or_default();
-    let udp = procfs::net::udp().unwrap_or_default();
-    
-    let tcp_connections = tcp.len();
-    let udp_connections = udp.len();
-    
-    let tcp_listen_ports: Vec<u16> = tcp.iter()
-        .filter(|conn| conn.state == TcpState::Listen)
-        .map(|conn| conn.local_address.port())
+fn read_sys_value(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path).ok().map(|value| value.trim().to_string()).filter(|value| !value.is_empty())
+}
+
+fn block_device_name(majmin: &str) -> Option<String> {
+    let path = std::fs::canonicalize(format!("/sys/dev/block/{}", majmin)).ok()?;
+    Some(path.file_name()?.to_string_lossy().into_owned())
+}
+
+// btrfs 等文件系统使用匿名设备号，退回到挂载源路径（如 /dev/mapper/vg-root -> dm-3）
+fn source_device_name(source: &str) -> Option<String> {
+    if !source.starts_with("/dev/") {
+        return None;
+    }
+    let path = std::fs::canonicalize(source).ok()?;
+    let name = path.file_name()?.to_string_lossy().into_owned();
+    Path::new("/sys/class/block").join(&name).exists().then_some(name)
+}
+
+fn dm_type(uuid: &str) -> Option<String> {
+    let (prefix, rest) = uuid.split_once('-')?;
+    Some(match prefix {
+        "CRYPT" if rest.starts_with("LUKS") => "luks".to_string(),
+        prefix => prefix.to_ascii_lowercase(),
+    })
+}
+
+// 按内核设备名索引的设备栈：slaves 给出 device-mapper 的底层设备，分区的父设备是其 sysfs 目录的上一级
+pub fn get_block_topology() -> HashMap<String, BlockNode> {
+    let Ok(entries) = std::fs::read_dir("/sys/class/block") else {
+        return HashMap::new();
+    };
+    entries.flatten().map(|entry| {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let path = entry.path();
+        let mut parents: Vec<String> = std::fs::read_dir(path.join("slaves"))
+            .map(|slaves| slaves.flatten().map(|slave| slave.file_name().to_string_lossy().into_owned()).collect())
+            .unwrap_or_default();
+        if path.join("partition").exists() {
+            let parent = std::fs::canonicalize(&path).ok()
+                .and_then(|real| Some(real.parent()?.file_name()?.to_string_lossy().into_owned()));
+            parents.extend(parent);
+        }
+        parents.sort();
+
+        let node = BlockNode {
+            dm_name: read_sys_value(&path.join("dm/name")),
+            dm_type: read_sys_value(&path.join("dm/uuid")).as_deref().and_then(dm_type),
+            parents,
+        };
+        (name, node)
+    }).collect()
+}
+
+// diskstats 中的扇区固定为 512 字节，与设备实际扇区大小无关
+const SECTOR_SIZE: u64 = 512;
+
+// 累计值直接取自 /proc/diskstats，速率、await、利用率和队列深度由与上一次采样的差值计算
+pub fn get_disk_io_stats(counters: &mut Counters) -> Vec<DiskIoStats> {
+    let Ok(disks) = procfs::diskstats() else {
+        return Vec::new();
+    };
+    let now = Instant::now();
+    let previous = counters.disks.take();
+
+    let stats = disks.iter().map(|disk| {
+        let mut stats = DiskIoStats {
+            device: disk.name.clone(),
+            partition: Path::new("/sys/class/block").join(&disk.name).join("partition").exists(),
+            reads: disk.reads,
+            writes: disk.writes,
+            read_bytes: disk.sectors_read * SECTOR_SIZE,
+            write_bytes: disk.sectors_written * SECTOR_SIZE,
+            read_time: disk.time_reading,
+            write_time: disk.time_writing,
+            io_in_progress: disk.in_progress,
+            io_time: disk.time_in_progress,
+            weighted_io_time: disk.weighted_time_in_progress,
+            discards: disk.discards,
+            discard_bytes: disk.sectors_discarded.map(|sectors| sectors * SECTOR_SIZE),
+            discard_time: disk.time_discarding,
+            flushes: disk.flushes,
+            flush_time: disk.time_flushing,
+            ..DiskIoStats::default()
+        };
+
+        let prev = previous.as_ref().and_then(|(at, disks)| {
+            Some((now.duration_since(*at).as_secs_f64(), disks.get(&disk.name)?))
+        });
+        if let Some((secs, prev)) = prev.filter(|(secs, _)| *secs > 0.0) {
+            disk_rates(disk, prev, secs, &mut stats);
+        }
+        stats
+    }).collect();
+
+    counters.disks = Some((now, disks.into_iter().map(|disk| (disk.name.clone(), disk)).collect()));
+    stats
+}
+
+// 任一计数器减小（同名设备被重建或计数器被重置）时这次采样没有速率，各速率保持为 null
+fn disk_rates(disk: &DiskStat, prev: &DiskStat, secs: f64, stats: &mut DiskIoStats) -> Option<()> {
+    // 外层 None 表示计数器回退，内层 None 表示内核不提供该计数器
+    let optional_delta = |current: Option<u64>, previous: Option<u64>| match (current, previous) {
+        (Some(current), Some(previous)) => counter_delta(current, previous).map(Some),
+        _ => Some(None),
+    };
+    let await_ms = |time: u64, ops: u64| (ops > 0).then(|| time as f64 / ops as f64);
+
+    let reads = counter_delta(disk.reads, prev.reads)?;
+    let writes = counter_delta(disk.writes, prev.writes)?;
+    let sectors_read = counter_delta(disk.sectors_read, prev.sectors_read)?;
+    let sectors_written = counter_delta(disk.sectors_written, prev.sectors_written)?;
+    let read_time = counter_delta(disk.time_reading, prev.time_reading)?;
+    let write_time = counter_delta(disk.time_writing, prev.time_writing)?;
+    let io_time = counter_delta(disk.time_in_progress, prev.time_in_progress)?;
+    let weighted_io_time = counter_delta(disk.weighted_time_in_progress, prev.weighted_time_in_progress)?;
+    let discards = optional_delta(disk.discards, prev.discards)?;
+    let flushes = optional_delta(disk.flushes, prev.flushes)?;
+    let discard_time = optional_delta(disk.time_discarding, prev.time_discarding)?;
+    let elapsed_ms = secs * 1000.0;
+
+    stats.read_iops = Some(reads as f64 / secs);
+    stats.write_iops = Some(writes as f64 / secs);
+    stats.discard_iops = discards.map(|ops| ops as f64 / secs);
+    stats.flush_iops = flushes.map(|ops| ops as f64 / secs);
+    stats.read_bytes_sec = Some(sectors_read as f64 * SECTOR_SIZE as f64 / secs);
+    stats.write_bytes_sec = Some(sectors_written as f64 * SECTOR_SIZE as f64 / secs);
+    stats.read_await = await_ms(read_time, reads);
+    stats.write_await = await_ms(write_time, writes);
+    // 与 iostat 一致，合计 await 包含 discard 但不包含 flush
+    stats.io_await = await_ms(
+        read_time + write_time + discard_time.unwrap_or(0),
+        reads + writes + discards.unwrap_or(0),
+    );
+    stats.util_percentage = Some((io_time as f64 / elapsed_ms * 100.0).min(100.0));
+    stats.queue_depth = Some(weighted_io_time as f64 / elapsed_ms);
+    Some(())
+}
+
+pub fn get_network_stats(_sys: &System, counters: &mut Counters) -> NetworkStats {
+    let tcp: Vec<TcpNetEntry> = procfs::net::tcp().unwrap_or_default()
+        .into_iter()
+        .chain(procfs::net::tcp6().unwrap_or_default())
+        .collect();
+    let udp: Vec<UdpNetEntry> = procfs::net::udp().unwrap_or_default()
+        .into_iter()
+        .chain(procfs::net::udp6().unwrap_or_default())
         .collect();
 
-    let udp_listen_ports: Vec<u16> = udp.iter()
-        .map(|conn| conn.local_address.port())
+    let mut listeners: Vec<Listener> = tcp.iter()
+        .filter(|conn| conn.state == TcpState::Listen)
+        .map(|conn| listener("tcp", conn.local_address))
+        // 未连接的 UDP 套接字远端为通配地址
+        .chain(udp.iter()
+            .filter(|conn| conn.remote_address.port() == 0 && conn.remote_address.ip().is_unspecified())
+            .map(|conn| listener("udp", conn.local_address)))
         .collect();
+    // SO_REUSEPORT 会让同一地址出现多个套接字
+    listeners.sort_by(|a, b| (a.port, &a.protocol, &a.address).cmp(&(b.port, &b.protocol, &b.address)));
+    listeners.dedup();
+
+    let ports = |protocol: &str| {
+        let mut ports: Vec<u16> = listeners.iter()
+            .filter(|l| l.protocol == protocol)
+            .map(|l| l.port)
+            .collect();
+        ports.dedup();
+        ports
+    };
 
     NetworkStats {
-        tcp_connections,
-        udp_connections,
-        tcp_listen_ports,
-        udp_listen_ports,
-        interface_stats: get_interface_stats(sys),
-    }
-}
-
-pub fn get_interface_stats(sys: &System) -> Vec<InterfaceStats> {
-    sys.networks().iter().map(|(name, _data)| {
-        InterfaceStats {
-            name: name.clone(),
-            rx_errors: 0,
-            tx_errors: 0,
-            rx_dropped: 0,
-            tx_dropped: 0,
-            rx_bytes_sec: 0.0,
-            tx_bytes_sec: 0.0,
-            rx_packets_sec: 0.0,
-            tx_packets_sec: 0.0,
+        tcp_connections: tcp.len(),
+        udp_connections: udp.len(),
+        tcp_listen_ports: ports("tcp"),
+        udp_listen_ports: ports("udp"),
+        listeners,
+        tcp_states: Some(tcp_states(&tcp)),
+        protocol_counters: get_protocol_counters(counters),
+        interface_stats: get_interface_stats(counters),
+    }
+}
+
+fn tcp_states(tcp: &[TcpNetEntry]) -> TcpStates {
+    let mut states = TcpStates::default();
+    for conn in tcp {
+        let count = match conn.state {
+            TcpState::Established => &mut states.established,
+            TcpState::SynSent => &mut states.syn_sent,
+            TcpState::SynRecv => &mut states.syn_recv,
+            TcpState::FinWait1 => &mut states.fin_wait1,
+            TcpState::FinWait2 => &mut states.fin_wait2,
+            TcpState::TimeWait => &mut states.time_wait,
+            TcpState::Close => &mut states.close,
+            TcpState::CloseWait => &mut states.close_wait,
+            TcpState::LastAck => &mut states.last_ack,
+            TcpState::Listen => &mut states.listen,
+            TcpState::Closing => &mut states.closing,
+            TcpState::NewSynRecv => &mut states.new_syn_recv,
+        };
+        *count += 1;
+    }
+    states
+}
+
+// /proc/net/snmp 和 /proc/net/netstat 由成对的表头行和数值行组成，键形如 "Tcp.RetransSegs"
+fn read_protocol_table(path: &str, values: &mut HashMap<String, i64>) {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return;
+    };
+    let mut lines = content.lines();
+    while let (Some(header), Some(data)) = (lines.next(), lines.next()) {
+        let (Some((prefix, keys)), Some((_, numbers))) = (header.split_once(':'), data.split_once(':')) else {
+            continue;
+        };
+        for (key, number) in keys.split_whitespace().zip(numbers.split_whitespace()) {
+            if let Ok(number) = number.parse() {
+                values.insert(format!("{}.{}", prefix, key), number);
+            }
         }
-    }).collect()
+    }
 }
 
-pub fn get_process_stats(sys: &System) -> ProcessStats {
-    let mut stats = ProcessStats {
-        zombie_count: 0,
-        sleeping_count: 0,
-        blocked_count: 0,
-        total_cpu_usage: 0.0,
-        total_memory_usage: 0,
+// /proc/net/sockstat 每行为 "TCP: inuse 4 orphan 0 tw 2 alloc 4 mem 0"，键形如 "TCP.inuse"
+fn read_sockstat(path: &str, values: &mut HashMap<String, i64>) {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return;
     };
+    for line in content.lines() {
+        let Some((prefix, fields)) = line.split_once(':') else {
+            continue;
+        };
+        let fields: Vec<&str> = fields.split_whitespace().collect();
+        for pair in fields.chunks(2) {
+            if let [key, number] = pair {
+                if let Ok(number) = number.parse() {
+                    values.insert(format!("{}.{}", prefix, key), number);
+                }
+            }
+        }
+    }
+}
 
-    for process in sys.processes().values() {
-        match process.status() {
-            sysinfo::ProcessStatus::Zombie => stats.zombie_count += 1,
-            sysinfo::ProcessStatus::Sleep => stats.sleeping_count += 1,
-            sysinfo::ProcessStatus::Stop => stats.blocked_count += 1,
-            _ => {}
+pub fn get_protocol_counters(counters: &mut Counters) -> Option<ProtocolCounters> {
+    let mut values = HashMap::new();
+    read_protocol_table("/proc/net/snmp", &mut values);
+    read_protocol_table("/proc/net/netstat", &mut values);
+    if values.is_empty() {
+        return None;
+    }
+    read_sockstat("/proc/net/sockstat", &mut values);
+    read_sockstat("/proc/net/sockstat6", &mut values);
+
+    let now = Instant::now();
+    let previous = counters.protocols.take();
+    let value = |values: &HashMap<String, i64>, key: &str| values.get(key).map_or(0, |v| (*v).max(0) as u64);
+    let delta = |key: &str| {
+        let (at, prev) = previous.as_ref()?;
+        let secs = now.duration_since(*at).as_secs_f64();
+        let delta = counter_delta(value(&values, key), value(prev, key))?;
+        (secs > 0.0).then_some((delta, secs))
+    };
+    let per_sec = |key: &str| delta(key).map(|(delta, secs)| delta as f64 / secs);
+    let current = |key: &str| value(&values, key);
+    // sockstat 中的 mem 以页为单位
+    let page_size = procfs::page_size();
+
+    let stats = ProtocolCounters {
+        tcp_out_segs: current("Tcp.OutSegs"),
+        tcp_retrans_segs: current("Tcp.RetransSegs"),
+        tcp_out_rsts: current("Tcp.OutRsts"),
+        tcp_estab_resets: current("Tcp.EstabResets"),
+        tcp_attempt_fails: current("Tcp.AttemptFails"),
+        tcp_listen_overflows: current("TcpExt.ListenOverflows"),
+        tcp_listen_drops: current("TcpExt.ListenDrops"),
+        udp_in_errors: current("Udp.InErrors"),
+        udp_rcvbuf_errors: current("Udp.RcvbufErrors"),
+        udp_no_ports: current("Udp.NoPorts"),
+        tcp_retrans_segs_sec: per_sec("Tcp.RetransSegs"),
+        tcp_out_rsts_sec: per_sec("Tcp.OutRsts"),
+        tcp_estab_resets_sec: per_sec("Tcp.EstabResets"),
+        tcp_attempt_fails_sec: per_sec("Tcp.AttemptFails"),
+        tcp_listen_overflows_sec: per_sec("TcpExt.ListenOverflows"),
+        tcp_listen_drops_sec: per_sec("TcpExt.ListenDrops"),
+        udp_in_errors_sec: per_sec("Udp.InErrors"),
+        udp_rcvbuf_errors_sec: per_sec("Udp.RcvbufErrors"),
+        udp_no_ports_sec: per_sec("Udp.NoPorts"),
+        tcp_retrans_percentage: delta("Tcp.OutSegs")
+            .filter(|(out, _)| *out > 0)
+            .zip(delta("Tcp.RetransSegs"))
+            .map(|((out, _), (retrans, _))| retrans as f64 / out as f64 * 100.0),
+        sockets_used: current("sockets.used"),
+        tcp_inuse: current("TCP.inuse") + current("TCP6.inuse"),
+        tcp_orphan: current("TCP.orphan"),
+        tcp_time_wait: current("TCP.tw"),
+        tcp_memory: current("TCP.mem") * page_size,
+        udp_inuse: current("UDP.inuse") + current("UDP6.inuse"),
+        udp_memory: current("UDP.mem") * page_size,
+    };
+
+    counters.protocols = Some((now, values));
+    Some(stats)
+}
+
+fn listener(protocol: &str, address: SocketAddr) -> Listener {
+    Listener {
+        protocol: protocol.to_string(),
+        address: address.ip().to_string(),
+        port: address.port(),
+    }
+}
+
+fn tcp_state_name(state: &TcpState) -> &'static str {
+    match state {
+        TcpState::Established => "ESTABLISHED",
+        TcpState::SynSent => "SYN_SENT",
+        TcpState::SynRecv => "SYN_RECV",
+        TcpState::FinWait1 => "FIN_WAIT1",
+        TcpState::FinWait2 => "FIN_WAIT2",
+        TcpState::TimeWait => "TIME_WAIT",
+        TcpState::Close => "CLOSE",
+        TcpState::CloseWait => "CLOSE_WAIT",
+        TcpState::LastAck => "LAST_ACK",
+        TcpState::Listen => "LISTEN",
+        TcpState::Closing => "CLOSING",
+        TcpState::NewSynRecv => "NEW_SYN_RECV",
+    }
+}
+
+fn connection(protocol: &str, local: SocketAddr, remote: SocketAddr, state: &str) -> Connection {
+    Connection {
+        protocol: protocol.to_string(),
+        local_address: local.ip().to_string(),
+        local_port: local.port(),
+        remote_address: remote.ip().to_string(),
+        remote_port: remote.port(),
+        state: state.to_string(),
+        ..Connection::default()
+    }
+}
+
+// 套接字 inode 到所属进程；同一套接字被多个进程持有时取 pid 最小的
+fn socket_owners(inodes: &HashSet<u64>) -> HashMap<u64, (u32, String)> {
+    let mut owners: HashMap<u64, (u32, String)> = HashMap::new();
+    let Ok(processes) = procfs::process::all_processes() else {
+        return owners;
+    };
+
+    for process in processes.flatten() {
+        // 其他用户的进程在非 root 下无法读取 fd 目录
+        let Ok(fds) = process.fd() else {
+            continue;
+        };
+        let mut name = None;
+        for fd in fds.flatten() {
+            let FDTarget::Socket(inode) = fd.target else {
+                continue;
+            };
+            if !inodes.contains(&inode) {
+                continue;
+            }
+            let name = name.get_or_insert_with(|| process.stat().map(|stat| stat.comm).unwrap_or_default());
+            let pid = process.pid as u32;
+            let owner = owners.entry(inode).or_insert_with(|| (pid, name.clone()));
+            if pid < owner.0 {
+                *owner = (pid, name.clone());
+            }
+        }
+    }
+    owners
+}
+
+pub fn get_connections(filter: impl Fn(&Connection) -> bool) -> Vec<Connection> {
+    let mut connections = Vec::new();
+    for entry in procfs::net::tcp().unwrap_or_default().into_iter().chain(procfs::net::tcp6().unwrap_or_default()) {
+        connections.push(Connection {
+            rx_queue: entry.rx_queue as u64,
+            tx_queue: entry.tx_queue as u64,
+            uid: entry.uid,
+            inode: entry.inode,
+            ..connection("tcp", entry.local_address, entry.remote_address, tcp_state_name(&entry.state))
+        });
+    }
+    for entry in procfs::net::udp().unwrap_or_default().into_iter().chain(procfs::net::udp6().unwrap_or_default()) {
+        // 与 ss 一致，未连接的 UDP 套接字显示为 UNCONN
+        let state = match entry.state {
+            UdpState::Established => "ESTABLISHED",
+            UdpState::Close => "UNCONN",
+        };
+        connections.push(Connection {
+            rx_queue: entry.rx_queue as u64,
+            tx_queue: entry.tx_queue as u64,
+            uid: entry.uid,
+            inode: entry.inode,
+            ..connection("udp", entry.local_address, entry.remote_address, state)
+        });
+    }
+    connections.retain(|conn| filter(conn));
+
+    // TIME_WAIT 等已脱离进程的套接字 inode 为 0
+    let inodes: HashSet<u64> = connections.iter().map(|conn| conn.inode).filter(|inode| *inode != 0).collect();
+    if !inodes.is_empty() {
+        let owners = socket_owners(&inodes);
+        for conn in &mut connections {
+            if let Some((pid, name)) = owners.get(&conn.inode) {
+                conn.pid = Some(*pid);
+                conn.process_name = Some(name.clone());
+            }
+        }
+    }
+
+    connections.sort_by(|a, b| {
+        (&a.protocol, a.local_port, &a.local_address, &a.remote_address, a.remote_port)
+            .cmp(&(&b.protocol, b.local_port, &b.local_address, &b.remote_address, b.remote_port))
+    });
+    connections
+}
+
+// 64 位内核上 /proc/net/dev、snmp、vmstat 和 diskstats 的计数器不会回绕，减小只能是被重置（如网卡重建），
+// 此时跳过这次采样，速率为 null
+fn counter_delta(current: u64, previous: u64) -> Option<u64> {
+    current.checked_sub(previous)
+}
+
+// 同名网卡被删除后重建时 ifindex 会变化，计数器从 0 重新开始
+fn read_ifindex(name: &str) -> Option<u32> {
+    read_net_attr(name, "ifindex")?.parse().ok()
+}
+
+// 错误和丢包为累计值，速率由与上一次采样的差值计算；首次出现或重建的网卡速率为 null
+pub fn get_interface_stats(counters: &mut Counters) -> Vec<InterfaceStats> {
+    let Ok(devices) = procfs::net::dev_status() else {
+        return Vec::new();
+    };
+    let now = Instant::now();
+    let previous = counters.network.take();
+
+    let devices: HashMap<String, InterfaceSample> = devices.into_iter()
+        .map(|(name, dev)| (name.clone(), (read_ifindex(&name), dev)))
+        .collect();
+
+    let mut stats: Vec<InterfaceStats> = devices.values().map(|(ifindex, dev)| {
+        let mut stats = InterfaceStats {
+            name: dev.name.clone(),
+            rx_errors: dev.recv_errs,
+            tx_errors: dev.sent_errs,
+            rx_dropped: dev.recv_drop,
+            tx_dropped: dev.sent_drop,
+            ..InterfaceStats::default()
+        };
+        let prev = previous.as_ref().and_then(|(at, devices)| {
+            let (prev_ifindex, prev) = devices.get(&dev.name)?;
+            (prev_ifindex == ifindex).then(|| (now.duration_since(*at).as_secs_f64(), prev))
+        });
+        if let Some((secs, prev)) = prev.filter(|(secs, _)| *secs > 0.0) {
+            let per_sec = |current, previous| Some(counter_delta(current, previous)? as f64 / secs);
+            stats.rx_bytes_sec = per_sec(dev.recv_bytes, prev.recv_bytes);
+            stats.tx_bytes_sec = per_sec(dev.sent_bytes, prev.sent_bytes);
+            stats.rx_packets_sec = per_sec(dev.recv_packets, prev.recv_packets);
+            stats.tx_packets_sec = per_sec(dev.sent_packets, prev.sent_packets);
+        }
+        stats
+    }).collect();
+    stats.sort_by(|a, b| a.name.cmp(&b.name));
+
+    // 只保留本次存在的网卡，消失的网卡不再参与计算
+    counters.network = Some((now, devices));
+    stats
+}
+
+// 按网卡名汇总的地址，格式为 "地址/前缀长度"
+pub fn get_interface_addresses() -> HashMap<String, Vec<String>> {
+    let mut entries = Vec::new();
+    let mut ifap: *mut libc::ifaddrs = std::ptr::null_mut();
+    if unsafe { libc::getifaddrs(&mut ifap) } != 0 {
+        return HashMap::new();
+    }
+
+    let mut cursor = ifap;
+    while let Some(ifa) = unsafe { cursor.as_ref() } {
+        cursor = ifa.ifa_next;
+        if let Some(address) = unsafe { format_address(ifa.ifa_addr, ifa.ifa_netmask) } {
+            let name = unsafe { CStr::from_ptr(ifa.ifa_name) }.to_string_lossy().into_owned();
+            entries.push((name, address));
+        }
+    }
+    unsafe { libc::freeifaddrs(ifap) };
+    group_addresses(entries)
+}
+
+// IPv4 标签别名（如 eth0:1）的地址归到所属网卡下，/proc/net/dev 中只有 eth0
+fn group_addresses(entries: Vec<(String, String)>) -> HashMap<String, Vec<String>> {
+    let mut addresses: HashMap<String, Vec<String>> = HashMap::new();
+    for (name, address) in entries {
+        let base = name.split_once(':').map_or(name.as_str(), |(base, _)| base);
+        addresses.entry(base.to_string()).or_default().push(address);
+    }
+    addresses
+}
+
+// 只处理 IPv4/IPv6，其余地址族（如 AF_PACKET）返回 None
+unsafe fn format_address(addr: *const libc::sockaddr, netmask: *const libc::sockaddr) -> Option<String> {
+    match addr.as_ref()?.sa_family as libc::c_int {
+        libc::AF_INET => {
+            let ip = (*(addr as *const libc::sockaddr_in)).sin_addr.s_addr;
+            let prefix = netmask.as_ref()
+                .map_or(32, |_| (*(netmask as *const libc::sockaddr_in)).sin_addr.s_addr.count_ones());
+            Some(format!("{}/{}", Ipv4Addr::from(u32::from_be(ip)), prefix))
+        }
+        libc::AF_INET6 => {
+            let ip = (*(addr as *const libc::sockaddr_in6)).sin6_addr.s6_addr;
+            let prefix = netmask.as_ref().map_or(128, |_| {
+                (*(netmask as *const libc::sockaddr_in6)).sin6_addr.s6_addr.iter().map(|b| b.count_ones()).sum()
+            });
+            Some(format!("{}/{}", Ipv6Addr::from(ip), prefix))
         }
+        _ => None,
+    }
+}
+
+fn read_net_attr(name: &str, attr: &str) -> Option<String> {
+    std::fs::read_to_string(format!("/sys/class/net/{}/{}", name, attr))
+        .ok()
+        .map(|value| value.trim().to_string())
+}
+
+pub fn get_link_info(name: &str) -> Option<LinkInfo> {
+    let operstate = read_net_attr(name, "operstate")?;
+    Some(LinkInfo {
+        operstate,
+        // 接口 down 时 carrier、speed、duplex 读取会返回 EINVAL
+        carrier: read_net_attr(name, "carrier").map(|carrier| carrier == "1"),
+        mtu: read_net_attr(name, "mtu").and_then(|mtu| mtu.parse().ok()),
+        // 未协商时内核报告 -1（SPEED_UNKNOWN）
+        speed: read_net_attr(name, "speed")
+            .and_then(|speed| speed.parse::<i64>().ok())
+            .and_then(|speed| u32::try_from(speed).ok())
+            .filter(|speed| *speed > 0),
+        duplex: read_net_attr(name, "duplex").filter(|duplex| duplex != "unknown"),
+        // 虚拟网卡没有 device 目录
+        driver: std::fs::read_link(format!("/sys/class/net/{}/device/driver", name))
+            .ok()
+            .and_then(|path| path.file_name().map(|n| n.to_string_lossy().into_owned())),
+    })
+}
+
+pub fn get_process_stats(sys: &System, tasks: Option<&TaskScan>) -> ProcessStats {
+    let mut stats = ProcessStats::default();
+    for process in sys.processes().values() {
         stats.total_cpu_usage += process.cpu_usage();
         stats.total_memory_usage += process.memory();
     }
 
+    match tasks {
+        Some(tasks) => {
+            stats.zombie_count = tasks.states.zombie;
+            stats.sleeping_count = tasks.states.sleeping;
+            // 阻塞指不可中断睡眠（D），而不是被停止
+            stats.blocked_count = tasks.states.disk_sleep;
+            stats.task_states = Some(tasks.states.clone());
+        }
+        // 无法遍历 /proc 时退回 sysinfo 的进程状态（只反映主线程）
+        None => {
+            for process in sys.processes().values() {
+                match process.status() {
+                    ProcessStatus::Zombie => stats.zombie_count += 1,
+                    ProcessStatus::Sleep => stats.sleeping_count += 1,
+                    ProcessStatus::UninterruptibleDiskSleep => stats.blocked_count += 1,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    if let Ok(kernel) = procfs::KernelStats::new() {
+        stats.procs_running = kernel.procs_running.map(u64::from);
+        stats.procs_blocked = kernel.procs_blocked.map(u64::from);
+    }
+
     stats
 }
 
+// 同一次遍历中读取每个线程的状态和每个进程的线程数；状态按 /proc/[pid]/task/[tid]/stat 统计，
+// 与 /proc/stat 的 procs_running、procs_blocked 一样以任务为单位
+pub fn scan_tasks() -> Option<TaskScan> {
+    let mut states = TaskStates::default();
+    let mut threads = HashMap::new();
+    for process in procfs::process::all_processes().ok()?.flatten() {
+        // 进程可能在遍历期间退出
+        let Ok(tasks) = process.tasks() else {
+            continue;
+        };
+        let mut count = 0;
+        for stat in tasks.filter_map(|task| task.ok()?.stat().ok()) {
+            count += 1;
+            match stat.state {
+                'R' => states.running += 1,
+                'S' => states.sleeping += 1,
+                'D' => states.disk_sleep += 1,
+                'T' => states.stopped += 1,
+                't' => states.tracing_stop += 1,
+                'Z' => states.zombie += 1,
+                'I' => states.idle += 1,
+                'X' | 'x' => states.dead += 1,
+                _ => {}
+            }
+        }
+        threads.insert(process.pid as u32, count);
+    }
+    Some(TaskScan { states, threads })
+}
+
+// 各进程的线程数
+pub fn get_thread_counts() -> Option<HashMap<u32, u64>> {
+    scan_tasks().map(|tasks| tasks.threads)
+}
+
 pub fn get_power_info() -> PowerInfo {
     PowerInfo {
         ac_powered: true,
@@ -126,34 +849,377 @@ pub fn get_power_info() -> PowerInfo {
     }
 }
 
-pub fn get_performance_metrics(sys: &System) -> PerformanceMetrics {
-    if let Ok(stat) = procfs::process::Process::myself() {
-        if let Ok(stat) = stat.stat() {
-            return PerformanceMetrics {
-                iowait_percentage: 0.0,
-                steal_percentage: 0.0,
-                system_percentage: stat.stime as f32,
-                user_percentage: stat.utime as f32,
-                nice_percentage: stat.priority as f32,
-                irq_percentage: 0.0,
-                softirq_percentage: 0.0,
-                cpu_queue_length: sys.processes().len() as u64,
-                context_switches: stat.num_threads as u64,
-                interrupts: 0,
-            };
+// /proc/stat 中一行 cpu 时间，单位为 tick
+#[derive(Clone, Copy, Default)]
+struct CpuTicks {
+    user: u64,
+    nice: u64,
+    system: u64,
+    idle: u64,
+    iowait: u64,
+    irq: u64,
+    softirq: u64,
+    steal: u64,
+    guest: u64,
+}
+
+impl CpuTicks {
+    fn parse<'a>(fields: impl Iterator<Item = &'a str>) -> Self {
+        let mut values = fields.map(|v| v.parse::<u64>().unwrap_or(0));
+        let mut next = || values.next().unwrap_or(0);
+        CpuTicks {
+            user: next(),
+            nice: next(),
+            system: next(),
+            idle: next(),
+            iowait: next(),
+            irq: next(),
+            softirq: next(),
+            steal: next(),
+            guest: next(),
         }
     }
 
-    PerformanceMetrics {
-        iowait_percentage: 0.0,
-        steal_percentage: 0.0,
-        system_percentage: sys.cpus().iter().map(|cpu| cpu.cpu_usage()).sum::<f32>() / sys.cpus().len() as f32,
-        user_percentage: 0.0,
-        nice_percentage: 0.0,
-        irq_percentage: 0.0,
-        softirq_percentage: 0.0,
-        cpu_queue_length: sys.processes().len() as u64,
+    // guest 时间已经计入 user，不重复累加
+    fn total(&self) -> u64 {
+        self.user + self.nice + self.system + self.idle + self.iowait + self.irq + self.softirq + self.steal
+    }
+
+    // iowait 等计数在 CPU 热插拔时可能回退，此时没有有意义的占比
+    fn since(&self, prev: &CpuTicks) -> Option<CpuTicks> {
+        Some(CpuTicks {
+            user: self.user.checked_sub(prev.user)?,
+            nice: self.nice.checked_sub(prev.nice)?,
+            system: self.system.checked_sub(prev.system)?,
+            idle: self.idle.checked_sub(prev.idle)?,
+            iowait: self.iowait.checked_sub(prev.iowait)?,
+            irq: self.irq.checked_sub(prev.irq)?,
+            softirq: self.softirq.checked_sub(prev.softirq)?,
+            steal: self.steal.checked_sub(prev.steal)?,
+            guest: self.guest.checked_sub(prev.guest)?,
+        })
+    }
+
+    // 与上一次采样之间各类时间的占比；没有上一次采样或计数器回退时为 None
+    fn percentages(&self, prev: Option<&CpuTicks>) -> [Option<f32>; 9] {
+        let Some(delta) = prev.and_then(|prev| self.since(prev)) else {
+            return [None; 9];
+        };
+        let total = delta.total().max(1) as f32;
+        [delta.user, delta.nice, delta.system, delta.idle, delta.iowait, delta.irq, delta.softirq, delta.steal, delta.guest]
+            .map(|v| Some(v as f32 / total * 100.0))
+    }
+}
+
+// 一次 CPU 相关计数器的采样
+#[derive(Clone)]
+struct CpuSample {
+    at: Instant,
+    total: CpuTicks,
+    // 各核按 cpuN 中的编号索引，热插拔后编号不连续
+    cores: BTreeMap<usize, CpuTicks>,
+    context_switches: u64,
+    interrupts: u64,
+    procs_running: u64,
+    core_interrupts: HashMap<usize, u64>,
+    core_schedules: Option<HashMap<usize, u64>>,
+}
+
+// 网卡计数器及采样时的 ifindex
+type InterfaceSample = (Option<u32>, DeviceStatus);
+
+// 上一次采样的累计计数器
+#[derive(Default, Clone)]
+pub struct Counters {
+    cpu: Option<CpuSample>,
+    network: Option<(Instant, HashMap<String, InterfaceSample>)>,
+    protocols: Option<(Instant, HashMap<String, i64>)>,
+    disks: Option<(Instant, HashMap<String, DiskStat>)>,
+    vmstat: Option<(Instant, HashMap<String, u64>)>,
+}
+
+fn read_cpu_sample() -> Option<CpuSample> {
+    let stat = std::fs::read_to_string("/proc/stat").ok()?;
+    let mut sample = CpuSample {
+        at: Instant::now(),
+        total: CpuTicks::default(),
+        cores: BTreeMap::new(),
         context_switches: 0,
         interrupts: 0,
+        procs_running: 0,
+        core_interrupts: read_core_interrupts().unwrap_or_default(),
+        core_schedules: read_core_schedules(),
+    };
+
+    for line in stat.lines() {
+        let mut fields = line.split_whitespace();
+        match fields.next() {
+            Some("cpu") => sample.total = CpuTicks::parse(fields),
+            Some(name) if name.starts_with("cpu") => {
+                if let Ok(cpu) = name["cpu".len()..].parse() {
+                    sample.cores.insert(cpu, CpuTicks::parse(fields));
+                }
+            }
+            Some("ctxt") => sample.context_switches = fields.next()?.parse().ok()?,
+            Some("intr") => sample.interrupts = fields.next()?.parse().ok()?,
+            Some("procs_running") => sample.procs_running = fields.next()?.parse().ok()?,
+            _ => {}
+        }
+    }
+    Some(sample)
+}
+
+// /proc/interrupts 中各 CPU 列的合计，表头为 "CPU0 CPU1 ..."
+fn read_core_interrupts() -> Option<HashMap<usize, u64>> {
+    let content = std::fs::read_to_string("/proc/interrupts").ok()?;
+    let mut lines = content.lines();
+    let cpus: Vec<usize> = lines.next()?
+        .split_whitespace()
+        .filter_map(|name| name.strip_prefix("CPU")?.parse().ok())
+        .collect();
+    let mut totals = vec![0u64; cpus.len()];
+    for line in lines {
+        let counts = line.split_whitespace().skip(1).take(cpus.len()).map_while(|v| v.parse::<u64>().ok());
+        for (total, count) in totals.iter_mut().zip(counts) {
+            *total += count;
+        }
+    }
+    Some(cpus.into_iter().zip(totals).collect())
+}
+
+// /proc/schedstat 中各 CPU 的 schedule() 调用次数；内核未编译 schedstats 时文件不存在，
+// kernel.sched_schedstats=0（默认）时计数不增长，两种情况都返回 None
+fn read_core_schedules() -> Option<HashMap<usize, u64>> {
+    let enabled = std::fs::read_to_string("/proc/sys/kernel/sched_schedstats")
+        .map_or(true, |value| value.trim() != "0");
+    if !enabled {
+        return None;
+    }
+    let content = std::fs::read_to_string("/proc/schedstat").ok()?;
+    Some(content.lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let cpu = fields.next()?.strip_prefix("cpu")?.parse().ok()?;
+            Some((cpu, fields.nth(2)?.parse().ok()?))
+        })
+        .collect())
+}
+
+// 计数器回退（如 CPU 热插拔）时没有有意义的速率
+fn rate(current: u64, previous: u64, seconds: f64) -> Option<f64> {
+    Some(current.checked_sub(previous)? as f64 / seconds)
+}
+
+// 每行为 "some avg10=3.31 avg60=4.72 avg300=3.50 total=195519422"
+fn parse_pressure_line(line: &str) -> Option<(&str, PressureStall)> {
+    let mut fields = line.split_whitespace();
+    let kind = fields.next()?;
+    let mut stall = PressureStall::default();
+    for field in fields {
+        let (key, value) = field.split_once('=')?;
+        match key {
+            "avg10" => stall.avg10 = value.parse().ok()?,
+            "avg60" => stall.avg60 = value.parse().ok()?,
+            "avg300" => stall.avg300 = value.parse().ok()?,
+            "total" => stall.total = value.parse().ok()?,
+            _ => {}
+        }
+    }
+    Some((kind, stall))
+}
+
+// 4.20 之前的内核没有 /proc/pressure；以 psi=0 启动时读取返回 EOPNOTSUPP
+fn read_pressure(resource: &str) -> Option<ResourcePressure> {
+    let content = std::fs::read_to_string(format!("/proc/pressure/{}", resource)).ok()?;
+    let mut some = None;
+    let mut full = None;
+    for (kind, stall) in content.lines().filter_map(parse_pressure_line) {
+        match kind {
+            "some" => some = Some(stall),
+            "full" => full = Some(stall),
+            _ => {}
+        }
+    }
+    Some(ResourcePressure { some: some?, full })
+}
+
+fn get_pressure() -> PressureInfo {
+    let cpu = read_pressure("cpu");
+    let memory = read_pressure("memory");
+    let io = read_pressure("io");
+    PressureInfo {
+        available: cpu.is_some() || memory.is_some() || io.is_some(),
+        cpu,
+        memory,
+        io,
+    }
+}
+
+pub fn get_performance_metrics(_sys: &System, counters: &mut Counters) -> PerformanceMetrics {
+    let Some(sample) = read_cpu_sample() else {
+        return PerformanceMetrics { pressure: get_pressure(), ..PerformanceMetrics::default() };
+    };
+    let previous = counters.cpu.take();
+    // 首次采样时没有基线，占比和速率都为 null
+    let elapsed = previous.as_ref()
+        .map(|prev| sample.at.duration_since(prev.at).as_secs_f64())
+        .filter(|&secs| secs > 0.0);
+    let per_sec = |current: u64, prev: Option<u64>| rate(current, prev?, elapsed?);
+
+    let [user, nice, system, idle, iowait, irq, softirq, steal, guest] =
+        sample.total.percentages(previous.as_ref().map(|p| &p.total));
+
+    let per_core = sample.cores.iter().map(|(&cpu, ticks)| {
+        let prev = previous.as_ref();
+        let [user, nice, system, idle, iowait, irq, softirq, steal, guest] =
+            ticks.percentages(prev.and_then(|p| p.cores.get(&cpu)));
+        CoreTimes {
+            cpu,
+            user_percentage: user,
+            nice_percentage: nice,
+            system_percentage: system,
+            idle_percentage: idle,
+            iowait_percentage: iowait,
+            irq_percentage: irq,
+            softirq_percentage: softirq,
+            steal_percentage: steal,
+            guest_percentage: guest,
+            context_switches_per_sec: sample.core_schedules.as_ref().and_then(|current| {
+                let prev = prev?.core_schedules.as_ref()?;
+                per_sec(*current.get(&cpu)?, prev.get(&cpu).copied())
+            }),
+            interrupts_per_sec: sample.core_interrupts.get(&cpu).and_then(|&current| {
+                per_sec(current, prev?.core_interrupts.get(&cpu).copied())
+            }),
+        }
+    }).collect();
+
+    let metrics = PerformanceMetrics {
+        iowait_percentage: iowait,
+        steal_percentage: steal,
+        system_percentage: system,
+        user_percentage: user,
+        nice_percentage: nice,
+        irq_percentage: irq,
+        softirq_percentage: softirq,
+        guest_percentage: guest,
+        idle_percentage: idle,
+        cpu_queue_length: sample.procs_running,
+        context_switches: sample.context_switches,
+        interrupts: sample.interrupts,
+        context_switches_per_sec: per_sec(sample.context_switches, previous.as_ref().map(|p| p.context_switches)),
+        interrupts_per_sec: per_sec(sample.interrupts, previous.as_ref().map(|p| p.interrupts)),
+        per_core,
+        pressure: get_pressure(),
+    };
+    counters.cpu = Some(sample);
+    metrics
+}
+
+const REDACTED: &str = "<redacted>";
+
+fn limit_value(value: LimitValue) -> Option<u64> {
+    match value {
+        LimitValue::Unlimited => None,
+        LimitValue::Value(v) => Some(v),
+    }
+}
+
+fn resource_limit(limit: &Limit) -> ResourceLimit {
+    ResourceLimit {
+        soft: limit_value(limit.soft_limit),
+        hard: limit_value(limit.hard_limit),
+    }
+}
+
+// 进程不存在时返回 None；单项读取失败（如权限不足）时对应字段为 None
+pub fn get_process_detail(pid: u32, show_env: bool) -> Option<ProcessDetail> {
+    let process = Process::new(pid as i32).ok()?;
+
+    let limits = process.limits().ok();
+    let status = process.status().ok();
+    let rollup = process.smaps_rollup().ok()
+        .and_then(|rollup| rollup.memory_map_rollup.memory_maps.into_iter().next())
+        .map(|map| map.extension.map);
+
+    Some(ProcessDetail {
+        pid,
+        cmdline: process.cmdline().ok(),
+        cwd: process.cwd().ok().map(|p| p.display().to_string()),
+        exe: process.exe().ok().map(|p| p.display().to_string()),
+        environment: process.environ().ok().map(|env| {
+            env.into_iter().map(|(key, value)| {
+                let value = if show_env { value.to_string_lossy().into_owned() } else { REDACTED.to_string() };
+                (key.to_string_lossy().into_owned(), value)
+            }).collect()
+        }),
+        fd_count: process.fd_count().ok(),
+        fd_limit: limits.as_ref().map(|l| resource_limit(&l.max_open_files)),
+        io: process.io().ok().map(|io| ProcessIo {
+            rchar: io.rchar,
+            wchar: io.wchar,
+            syscr: io.syscr,
+            syscw: io.syscw,
+            read_bytes: io.read_bytes,
+            write_bytes: io.write_bytes,
+            cancelled_write_bytes: io.cancelled_write_bytes,
+        }),
+        cgroups: process.cgroups().ok().map(|cgroups| {
+            cgroups.into_iter().map(|cgroup| ProcessCgroup {
+                hierarchy: cgroup.hierarchy,
+                controllers: cgroup.controllers,
+                path: cgroup.pathname,
+            }).collect()
+        }),
+        namespaces: process.namespaces().ok().map(|namespaces| {
+            namespaces.into_iter()
+                .map(|(name, ns)| (name.to_string_lossy().into_owned(), ns.identifier))
+                .collect()
+        }),
+        limits: limits.as_ref().map(|l| {
+            [
+                ("max_cpu_time", &l.max_cpu_time),
+                ("max_file_size", &l.max_file_size),
+                ("max_data_size", &l.max_data_size),
+                ("max_stack_size", &l.max_stack_size),
+                ("max_core_file_size", &l.max_core_file_size),
+                ("max_resident_set", &l.max_resident_set),
+                ("max_processes", &l.max_processes),
+                ("max_open_files", &l.max_open_files),
+                ("max_locked_memory", &l.max_locked_memory),
+                ("max_address_space", &l.max_address_space),
+                ("max_file_locks", &l.max_file_locks),
+                ("max_pending_signals", &l.max_pending_signals),
+                ("max_msgqueue_size", &l.max_msgqueue_size),
+                ("max_nice_priority", &l.max_nice_priority),
+                ("max_realtime_priority", &l.max_realtime_priority),
+                ("max_realtime_timeout", &l.max_realtime_timeout),
+            ].into_iter().map(|(name, limit)| (name.to_string(), resource_limit(limit))).collect::<BTreeMap<_, _>>()
+        }),
+        voluntary_ctxt_switches: status.as_ref().and_then(|s| s.voluntary_ctxt_switches),
+        nonvoluntary_ctxt_switches: status.as_ref().and_then(|s| s.nonvoluntary_ctxt_switches),
+        memory_pss: rollup.as_ref().and_then(|m| m.get("Pss").copied()),
+        // USS：只属于该进程的私有页
+        memory_uss: rollup.as_ref().and_then(|m| {
+            Some(m.get("Private_Clean")? + m.get("Private_Dirty")?)
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_label_aliases_under_interface() {
+        let addresses = group_addresses(vec![
+            ("eth0".to_string(), "10.0.0.1/24".to_string()),
+            ("eth0:1".to_string(), "10.0.0.2/24".to_string()),
+            ("eth0".to_string(), "fe80::1/64".to_string()),
+            ("lo".to_string(), "127.0.0.1/8".to_string()),
+        ]);
+        assert_eq!(addresses.len(), 2);
+        assert_eq!(addresses["eth0"], ["10.0.0.1/24", "10.0.0.2/24", "fe80::1/64"]);
+        assert_eq!(addresses["lo"], ["127.0.0.1/8"]);
     }
-} 
\ No newline at end of file
+}
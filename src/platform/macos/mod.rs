@@ -1,28 +1,60 @@
-use crate::{GpuInfo, DiskIoStats, NetworkStats, ProcessStats, PowerInfo, PerformanceMetrics};
+use crate::{GpuInfo, DiskIoStats, NetworkStats, ProcessStats, PowerInfo, PerformanceMetrics, ProcessDetail, LinkInfo, Connection, DiskInfo, MemoryDetails, PressureInfo};
 use sysinfo::{System, ProcessExt};
 use core_foundation::base::TCFType;
 use core_foundation::dictionary::CFDictionary;
 use core_foundation::string::CFString;
 use core_foundation::number::CFNumber;
+use std::collections::HashMap;
+
+pub const CAPABILITIES: super::Capabilities = super::Capabilities {
+    disk_io: false,
+    connections: false,
+    interface_counters: false,
+    cpu_time: false,
+    load_average: true,
+    thread_count: false,
+    power: false,
+    process_detail: false,
+    block_inventory: false,
+};
 
 pub fn get_gpu_info() -> Option<GpuInfo> {
     None // macOS GPU 信息需要使用 IOKit 获取
 }
 
-pub fn get_disk_io_stats() -> Vec<DiskIoStats> {
+pub fn get_filesystems() -> Option<Vec<DiskInfo>> {
+    None
+}
+
+pub fn get_block_topology() -> HashMap<String, super::BlockNode> {
+    HashMap::new()
+}
+
+pub fn get_memory_details(_counters: &mut Counters) -> Option<MemoryDetails> {
+    None
+}
+
+pub fn get_disk_io_stats(_counters: &mut Counters) -> Vec<DiskIoStats> {
     Vec::new() // macOS 磁盘 IO 统计需要使用 IOKit 获取
 }
 
-pub fn get_network_stats(sys: &System) -> NetworkStats {
+pub fn get_network_stats(sys: &System, _counters: &mut Counters) -> NetworkStats {
     NetworkStats {
         tcp_connections: 0,
         udp_connections: 0,
         tcp_listen_ports: Vec::new(),
         udp_listen_ports: Vec::new(),
+        listeners: Vec::new(),
+        tcp_states: None,
+        protocol_counters: None,
         interface_stats: get_interface_stats(sys),
     }
 }
 
+pub fn get_connections(_filter: impl Fn(&Connection) -> bool) -> Vec<Connection> {
+    Vec::new()
+}
+
 pub fn get_interface_stats(sys: &System) -> Vec<crate::InterfaceStats> {
     sys.networks().iter().map(|(name, _data)| {
         crate::InterfaceStats {
@@ -31,21 +63,32 @@ pub fn get_interface_stats(sys: &System) -> Vec<crate::InterfaceStats> {
             tx_errors: 0,
             rx_dropped: 0,
             tx_dropped: 0,
-            rx_bytes_sec: 0.0,
-            tx_bytes_sec: 0.0,
-            rx_packets_sec: 0.0,
-            tx_packets_sec: 0.0,
+            rx_bytes_sec: None,
+            tx_bytes_sec: None,
+            rx_packets_sec: None,
+            tx_packets_sec: None,
         }
     }).collect()
 }
 
-pub fn get_process_stats(sys: &System) -> ProcessStats {
+pub fn get_interface_addresses() -> HashMap<String, Vec<String>> {
+    HashMap::new()
+}
+
+pub fn get_link_info(_name: &str) -> Option<LinkInfo> {
+    None
+}
+
+pub fn get_process_stats(sys: &System, _tasks: Option<&super::TaskScan>) -> ProcessStats {
     let mut stats = ProcessStats {
         zombie_count: 0,
         sleeping_count: 0,
         blocked_count: 0,
         total_cpu_usage: 0.0,
         total_memory_usage: 0,
+        task_states: None,
+        procs_running: None,
+        procs_blocked: None,
     };
 
     for process in sys.processes().values() {
@@ -62,6 +105,14 @@ pub fn get_process_stats(sys: &System) -> ProcessStats {
     stats
 }
 
+pub fn scan_tasks() -> Option<super::TaskScan> {
+    None
+}
+
+pub fn get_thread_counts() -> Option<HashMap<u32, u64>> {
+    None
+}
+
 pub fn get_power_info() -> PowerInfo {
     PowerInfo {
         ac_powered: true,
@@ -72,17 +123,31 @@ pub fn get_power_info() -> PowerInfo {
     }
 }
 
-pub fn get_performance_metrics(sys: &System) -> PerformanceMetrics {
+// 本平台没有需要跨采样保存的计数器
+#[derive(Default, Clone)]
+pub struct Counters;
+
+pub fn get_performance_metrics(sys: &System, _counters: &mut Counters) -> PerformanceMetrics {
     PerformanceMetrics {
-        iowait_percentage: 0.0,
-        steal_percentage: 0.0,
-        system_percentage: sys.cpus().iter().map(|cpu| cpu.cpu_usage()).sum::<f32>() / sys.cpus().len() as f32,
-        user_percentage: 0.0,
-        nice_percentage: 0.0,
-        irq_percentage: 0.0,
-        softirq_percentage: 0.0,
+        iowait_percentage: None,
+        steal_percentage: None,
+        system_percentage: Some(sys.cpus().iter().map(|cpu| cpu.cpu_usage()).sum::<f32>() / sys.cpus().len() as f32),
+        user_percentage: None,
+        nice_percentage: None,
+        irq_percentage: None,
+        softirq_percentage: None,
+        guest_percentage: None,
+        idle_percentage: None,
         cpu_queue_length: sys.processes().len() as u64,
         context_switches: 0,
         interrupts: 0,
+        context_switches_per_sec: None,
+        interrupts_per_sec: None,
+        per_core: Vec::new(),
+        pressure: PressureInfo::default(),
     }
-} 
\ No newline at end of file
+}
+
+pub fn get_process_detail(_pid: u32, _show_env: bool) -> Option<ProcessDetail> {
+    None
+}
@@ -1,3 +1,6 @@
+use crate::TaskStates;
+use std::collections::HashMap;
+
 #[cfg(target_os = "linux")]
 mod linux;
 #[cfg(target_os = "linux")]
@@ -11,4 +14,33 @@ pub use self::windows::*;
 #[cfg(target_os = "macos")]
 mod macos;
 #[cfg(target_os = "macos")]
-pub use self::macos::*; 
\ No newline at end of file
+pub use self::macos::*;
+
+// 各平台实际采集的数据；未采集的部分在 v2 接口中输出 null，不支持的接口返回 501
+pub struct Capabilities {
+    pub disk_io: bool,
+    pub connections: bool,
+    pub interface_counters: bool,
+    pub cpu_time: bool,
+    // Windows 没有负载均值，sysinfo 返回 0
+    pub load_average: bool,
+    pub thread_count: bool,
+    pub power: bool,
+    pub process_detail: bool,
+    pub block_inventory: bool,
+}
+
+// 块设备在设备栈中的位置：分区的父设备、device-mapper 的底层设备
+#[derive(Default)]
+pub struct BlockNode {
+    pub dm_name: Option<String>,
+    // 由 dm/uuid 前缀得出，如 lvm、luks、mpath
+    pub dm_type: Option<String>,
+    pub parents: Vec<String>,
+}
+
+// 一次遍历 /proc 得到的各状态任务数和各进程线程数
+pub struct TaskScan {
+    pub states: TaskStates,
+    pub threads: HashMap<u32, u64>,
+}
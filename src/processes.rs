@@ -0,0 +1,321 @@
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use sysinfo::{Pid, PidExt, Process, ProcessExt, System, SystemExt, Uid, UserExt};
+
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessSort {
+    #[default]
+    Pid,
+    Cpu,
+    Mem,
+    Io,
+}
+
+#[derive(Deserialize)]
+pub struct ProcessQuery {
+    sort: Option<ProcessSort>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    // 进程名正则
+    name: Option<String>,
+    // 用户名或 uid
+    user: Option<String>,
+    state: Option<String>,
+}
+
+#[derive(Serialize, JsonSchema, Clone, Default)]
+pub struct ProcessEntry {
+    pid: u32,
+    ppid: Option<u32>,
+    name: String,
+    user: Option<String>,
+    state: String,
+    #[schemars(extend("x-unit" = "%"))]
+    cpu_usage: f32,
+    #[schemars(extend("x-unit" = "bytes"))]
+    memory_rss: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    memory_virtual: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    disk_read_bytes: u64,
+    #[schemars(extend("x-unit" = "bytes"))]
+    disk_written_bytes: u64,
+    #[schemars(extend("x-unit" = "s"))]
+    start_time: u64,
+    cmdline: Vec<String>,
+}
+
+#[derive(Serialize, JsonSchema, Clone, Default)]
+pub struct ProcessList {
+    // 过滤后、分页前的进程数
+    total: usize,
+    offset: usize,
+    processes: Vec<ProcessEntry>,
+}
+
+// 解析后的列表参数，可以移动到阻塞线程中执行
+pub struct ProcessListing {
+    sort: ProcessSort,
+    limit: Option<usize>,
+    offset: usize,
+    name: Option<Regex>,
+    user: Option<String>,
+    state: Option<String>,
+}
+
+impl ProcessListing {
+    pub fn from_query(query: &ProcessQuery) -> Result<Self, String> {
+        let name = query.name.as_deref()
+            .map(Regex::new)
+            .transpose()
+            .map_err(|e| format!("Invalid name pattern: {}", e))?;
+
+        Ok(ProcessListing {
+            sort: query.sort.unwrap_or_default(),
+            limit: query.limit,
+            offset: query.offset.unwrap_or(0),
+            name,
+            user: query.user.clone(),
+            state: query.state.clone(),
+        })
+    }
+
+    fn matches(&self, sys: &System, process: &Process) -> bool {
+        if self.name.as_ref().is_some_and(|re| !re.is_match(process.name())) {
+            return false;
+        }
+        if self.state.as_ref().is_some_and(|state| !state.eq_ignore_ascii_case(&process.status().to_string())) {
+            return false;
+        }
+        match &self.user {
+            Some(user) => process.user_id().is_some_and(|uid| {
+                user.parse::<Uid>().is_ok_and(|u| &u == uid)
+                    || user_name(sys, process).is_some_and(|name| name == user)
+            }),
+            None => true,
+        }
+    }
+
+    pub fn apply(&self, sys: &System) -> ProcessList {
+        let mut processes: Vec<&Process> = sys.processes().values()
+            .filter(|p| self.matches(sys, p))
+            .collect();
+
+        match self.sort {
+            ProcessSort::Pid => processes.sort_by_key(|p| p.pid().as_u32()),
+            ProcessSort::Cpu => processes.sort_by(|a, b| b.cpu_usage().total_cmp(&a.cpu_usage())),
+            ProcessSort::Mem => processes.sort_by_key(|p| Reverse(p.memory())),
+            ProcessSort::Io => processes.sort_by_key(|p| {
+                let io = p.disk_usage();
+                Reverse(io.total_read_bytes + io.total_written_bytes)
+            }),
+        }
+
+        ProcessList {
+            total: processes.len(),
+            offset: self.offset,
+            processes: processes.into_iter()
+                .skip(self.offset)
+                .take(self.limit.unwrap_or(usize::MAX))
+                .map(|p| entry(sys, p))
+                .collect(),
+        }
+    }
+}
+
+fn user_name<'a>(sys: &'a System, process: &Process) -> Option<&'a str> {
+    process.user_id()
+        .and_then(|uid| sys.get_user_by_id(uid))
+        .map(|user| user.name())
+}
+
+fn entry(sys: &System, process: &Process) -> ProcessEntry {
+    let io = process.disk_usage();
+    ProcessEntry {
+        pid: process.pid().as_u32(),
+        ppid: process.parent().map(Pid::as_u32),
+        name: process.name().to_string(),
+        user: user_name(sys, process).map(str::to_string),
+        state: process.status().to_string(),
+        cpu_usage: process.cpu_usage(),
+        memory_rss: process.memory(),
+        memory_virtual: process.virtual_memory(),
+        disk_read_bytes: io.total_read_bytes,
+        disk_written_bytes: io.total_written_bytes,
+        start_time: process.start_time(),
+        cmdline: process.cmd().to_vec(),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ProcessTreeQuery {
+    pid: Option<u32>,
+    // 以所有同名进程为根，嵌套在同名祖先下的不单独作为根
+    name: Option<String>,
+}
+
+#[derive(Serialize, JsonSchema, Clone, Default)]
+pub struct ProcessNode {
+    pid: u32,
+    name: String,
+    #[schemars(extend("x-unit" = "%"))]
+    cpu_usage: f32,
+    #[schemars(extend("x-unit" = "bytes"))]
+    memory_rss: u64,
+    threads: Option<u64>,
+    subtree: SubtreeTotals,
+    children: Vec<ProcessNode>,
+}
+
+// 包含节点自身在内的整棵子树合计
+#[derive(Serialize, JsonSchema, Clone, Default)]
+pub struct SubtreeTotals {
+    processes: usize,
+    #[schemars(extend("x-unit" = "%"))]
+    cpu_usage: f32,
+    #[schemars(extend("x-unit" = "bytes"))]
+    memory_rss: u64,
+    threads: Option<u64>,
+}
+
+#[derive(Serialize, JsonSchema, Clone, Default)]
+pub struct ProcessTree {
+    roots: Vec<ProcessNode>,
+}
+
+struct TreeBuilder<'a> {
+    sys: &'a System,
+    children: HashMap<Pid, Vec<Pid>>,
+    threads: Option<HashMap<u32, u64>>,
+    visited: HashSet<Pid>,
+}
+
+// 构建中的节点：pending 为尚未访问的子进程，children 为已完成的子树
+struct Frame<'a> {
+    pid: Pid,
+    process: &'a Process,
+    pending: Vec<Pid>,
+    children: Vec<ProcessNode>,
+}
+
+impl<'a> TreeBuilder<'a> {
+    fn frame(&mut self, pid: Pid) -> Option<Frame<'a>> {
+        // 防止异常的父子关系形成环
+        if !self.visited.insert(pid) {
+            return None;
+        }
+        let sys = self.sys;
+        Some(Frame {
+            pid,
+            process: sys.process(pid)?,
+            pending: self.children.get(&pid).cloned().unwrap_or_default(),
+            children: Vec::new(),
+        })
+    }
+
+    // 用显式栈按后序构建，很深的 fork 链不会耗尽工作线程的栈
+    fn node(&mut self, root: Pid) -> Option<ProcessNode> {
+        let mut stack = vec![self.frame(root)?];
+        loop {
+            let top = stack.last_mut()?;
+            if let Some(child) = top.pending.pop() {
+                if let Some(frame) = self.frame(child) {
+                    stack.push(frame);
+                }
+                continue;
+            }
+
+            let node = self.finish(stack.pop()?);
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(node),
+                None => return Some(node),
+            }
+        }
+    }
+
+    fn finish(&self, frame: Frame) -> ProcessNode {
+        let Frame { pid, process, mut children, .. } = frame;
+        let threads = self.threads.as_ref().and_then(|t| t.get(&pid.as_u32()).copied());
+        children.sort_by_key(|c| c.pid);
+
+        let mut subtree = SubtreeTotals {
+            processes: 1,
+            cpu_usage: process.cpu_usage(),
+            memory_rss: process.memory(),
+            threads,
+        };
+        for child in &children {
+            subtree.processes += child.subtree.processes;
+            subtree.cpu_usage += child.subtree.cpu_usage;
+            subtree.memory_rss += child.subtree.memory_rss;
+            // 任一进程的线程数未知时合计也未知
+            subtree.threads = subtree.threads.zip(child.subtree.threads).map(|(a, b)| a + b);
+        }
+
+        ProcessNode {
+            pid: pid.as_u32(),
+            name: process.name().to_string(),
+            cpu_usage: process.cpu_usage(),
+            memory_rss: process.memory(),
+            threads,
+            subtree,
+            children,
+        }
+    }
+}
+
+// 根据父进程关系构建进程树；找不到指定的根时返回 None
+pub fn build_tree(sys: &System, query: &ProcessTreeQuery, threads: Option<HashMap<u32, u64>>) -> Option<ProcessTree> {
+    let processes = sys.processes();
+    let mut children: HashMap<Pid, Vec<Pid>> = HashMap::new();
+    for (pid, process) in processes {
+        if let Some(parent) = process.parent().filter(|p| processes.contains_key(p)) {
+            children.entry(parent).or_default().push(*pid);
+        }
+    }
+
+    let has_ancestor = |process: &Process, matches: &dyn Fn(&Process) -> bool| {
+        let mut seen = HashSet::new();
+        let mut parent = process.parent();
+        while let Some(pid) = parent.filter(|p| seen.insert(*p)) {
+            match processes.get(&pid) {
+                Some(p) if matches(p) => return true,
+                Some(p) => parent = p.parent(),
+                None => return false,
+            }
+        }
+        false
+    };
+
+    let mut roots: Vec<Pid> = match (query.pid, &query.name) {
+        (Some(pid), _) => {
+            let pid = Pid::from_u32(pid);
+            processes.contains_key(&pid).then_some(vec![pid])?
+        }
+        (None, Some(name)) => {
+            let matches = |p: &Process| p.name() == name;
+            let roots: Vec<Pid> = processes.iter()
+                .filter(|(_, p)| matches(p) && !has_ancestor(p, &matches))
+                .map(|(pid, _)| *pid)
+                .collect();
+            if roots.is_empty() {
+                return None;
+            }
+            roots
+        }
+        (None, None) => processes.iter()
+            .filter(|(_, p)| p.parent().is_none_or(|parent| !processes.contains_key(&parent)))
+            .map(|(pid, _)| *pid)
+            .collect(),
+    };
+    roots.sort_by_key(|pid| pid.as_u32());
+
+    let mut builder = TreeBuilder { sys, children, threads, visited: HashSet::new() };
+    Some(ProcessTree {
+        roots: roots.into_iter().filter_map(|pid| builder.node(pid)).collect(),
+    })
+}
@@ -0,0 +1,227 @@
+use crate::collector::Subsystem;
+use crate::openapi;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+// SystemInfo 顶层字段及其依赖的子系统
+const FIELDS: &[(&str, &[Subsystem])] = &[
+    ("cpu_usage", &[Subsystem::Cpu]),
+    ("cpu_temp", &[Subsystem::Cpu, Subsystem::Sensors]),
+    ("cpu_brand", &[Subsystem::Cpu]),
+    ("cpu_frequency", &[Subsystem::Cpu]),
+    ("cpu_cores", &[Subsystem::Cpu]),
+    ("cpu_physical_cores", &[Subsystem::Cpu]),
+    ("cpu_vendor_id", &[Subsystem::Cpu]),
+    ("cpu_load_avg", &[Subsystem::Cpu]),
+    ("gpu_info", &[Subsystem::Gpu]),
+    ("memory_total", &[Subsystem::Memory]),
+    ("memory_used", &[Subsystem::Memory]),
+    ("memory_free", &[Subsystem::Memory]),
+    ("memory_available", &[Subsystem::Memory]),
+    ("memory_usage", &[Subsystem::Memory]),
+    ("swap_total", &[Subsystem::Memory]),
+    ("swap_used", &[Subsystem::Memory]),
+    ("swap_free", &[Subsystem::Memory]),
+    ("swap_usage", &[Subsystem::Memory]),
+    ("memory_details", &[Subsystem::Memory]),
+    ("disks", &[Subsystem::Disks]),
+    ("total_disk_space", &[Subsystem::Disks]),
+    ("total_disk_used", &[Subsystem::Disks]),
+    ("total_disk_free", &[Subsystem::Disks]),
+    ("disk_io_stats", &[Subsystem::Disks]),
+    ("system_name", &[Subsystem::Host]),
+    ("kernel_version", &[Subsystem::Host]),
+    ("os_version", &[Subsystem::Host]),
+    ("host_name", &[Subsystem::Host]),
+    ("boot_time", &[Subsystem::Host]),
+    ("uptime", &[Subsystem::Host]),
+    ("load_average", &[Subsystem::Cpu]),
+    ("process_count", &[Subsystem::Processes]),
+    ("thread_count", &[Subsystem::Processes]),
+    ("running_process_count", &[Subsystem::Processes]),
+    ("process_stats", &[Subsystem::Processes]),
+    ("temperatures", &[Subsystem::Sensors]),
+    ("temp_warnings", &[Subsystem::Sensors]),
+    ("timestamp", &[]),
+    ("networks", &[Subsystem::Network]),
+    ("total_rx_bytes", &[Subsystem::Network]),
+    ("total_tx_bytes", &[Subsystem::Network]),
+    ("network_stats", &[Subsystem::Network]),
+    ("power_info", &[Subsystem::Power]),
+    ("performance_metrics", &[Subsystem::Performance]),
+];
+
+#[derive(Deserialize)]
+pub struct ProjectionQuery {
+    fields: Option<String>,
+    exclude: Option<String>,
+}
+
+// 字段路径树，`all` 表示选中该节点下的整个子树
+#[derive(Default)]
+struct FieldTree {
+    all: bool,
+    children: BTreeMap<String, FieldTree>,
+}
+
+impl FieldTree {
+    fn parse(list: &str) -> Result<Self, String> {
+        let mut tree = FieldTree::default();
+        for path in list.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let top = path.split('.').next().unwrap_or_default();
+            if !FIELDS.iter().any(|(name, _)| *name == top) {
+                return Err(format!("Unknown field: {}", top));
+            }
+            if !known_path(path) {
+                return Err(format!("Unknown field: {}", path));
+            }
+
+            let mut node = &mut tree;
+            for part in path.split('.') {
+                node = node.children.entry(part.to_string()).or_default();
+            }
+            node.all = true;
+        }
+        Ok(tree)
+    }
+
+    // 只保留树中选中的字段；数组按元素逐个裁剪
+    fn retain(&self, value: &mut Value) {
+        if self.all {
+            return;
+        }
+        match value {
+            Value::Object(map) => {
+                map.retain(|key, _| self.children.contains_key(key));
+                for (key, child) in map.iter_mut() {
+                    self.children[key].retain(child);
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.retain(item)),
+            _ => {}
+        }
+    }
+
+    // 删除树中选中的字段；数组按元素逐个裁剪
+    fn remove(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, node) in &self.children {
+                    if node.all {
+                        map.remove(key);
+                    } else if let Some(child) = map.get_mut(key) {
+                        node.remove(child);
+                    }
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.remove(item)),
+            _ => {}
+        }
+    }
+}
+
+// 嵌套路径按 SystemInfo 的 JSON Schema 检查，数组和可空字段按其元素类型继续匹配
+fn known_path(path: &str) -> bool {
+    static SCHEMA: OnceLock<Value> = OnceLock::new();
+    let schema = SCHEMA.get_or_init(|| openapi::json_schema("SystemInfo").unwrap_or_default());
+    let defs = &schema["$defs"];
+
+    let mut node = schema;
+    for part in path.split('.') {
+        match element_schema(node, defs).get("properties").and_then(|p| p.get(part)) {
+            Some(child) => node = child,
+            None => return false,
+        }
+    }
+    true
+}
+
+// 跳过引用、可空包装和数组，得到描述字段本身的 schema
+fn element_schema<'a>(mut schema: &'a Value, defs: &'a Value) -> &'a Value {
+    loop {
+        let non_null = schema.get("anyOf").and_then(Value::as_array)
+            .and_then(|variants| variants.iter().find(|v| v.get("type") != Some(&json!("null"))));
+        if let Some(name) = schema.get("$ref").and_then(Value::as_str).and_then(|r| r.rsplit('/').next()) {
+            schema = &defs[name];
+        } else if let Some(variant) = non_null {
+            schema = variant;
+        } else if let Some(items) = schema.get("items") {
+            schema = items;
+        } else {
+            return schema;
+        }
+    }
+}
+
+// /api/system 的字段投影：`fields` 选择保留的字段，`exclude` 删除字段，均支持 `a.b` 形式的嵌套路径
+pub struct FieldSelector {
+    include: Option<FieldTree>,
+    exclude: FieldTree,
+}
+
+impl FieldSelector {
+    pub fn from_query(query: &ProjectionQuery) -> Result<Self, String> {
+        let include = match query.fields.as_deref() {
+            Some(fields) if !fields.trim().is_empty() => Some(FieldTree::parse(fields)?),
+            _ => None,
+        };
+        let exclude = FieldTree::parse(query.exclude.as_deref().unwrap_or_default())?;
+        Ok(FieldSelector { include, exclude })
+    }
+
+    // 没有任何投影参数时直接返回完整快照
+    pub fn is_identity(&self) -> bool {
+        self.include.is_none() && self.exclude.children.is_empty()
+    }
+
+    fn selects(&self, field: &str) -> bool {
+        let included = self.include.as_ref()
+            .is_none_or(|tree| tree.children.contains_key(field));
+        let excluded = self.exclude.children.get(field).is_some_and(|node| node.all);
+        included && !excluded
+    }
+
+    // 是否需要采集某个子系统：至少有一个依赖它的字段被选中
+    fn wants(&self, subsystem: Subsystem) -> bool {
+        FIELDS.iter()
+            .any(|(field, deps)| deps.contains(&subsystem) && self.selects(field))
+    }
+
+    pub fn wanted_subsystems(&self) -> Vec<Subsystem> {
+        Subsystem::ALL.into_iter().filter(|&s| self.wants(s)).collect()
+    }
+
+    pub fn apply(&self, mut value: Value) -> Value {
+        if let Some(include) = &self.include {
+            include.retain(&mut value);
+        }
+        self.exclude.remove(&mut value);
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SystemInfo;
+
+    #[test]
+    fn fields_cover_system_info() {
+        let value = serde_json::to_value(SystemInfo::default()).unwrap();
+        let mut keys: Vec<&str> = value.as_object().unwrap().keys().map(String::as_str).collect();
+        let mut fields: Vec<&str> = FIELDS.iter().map(|(name, _)| *name).collect();
+        keys.sort();
+        fields.sort();
+        assert_eq!(keys, fields);
+    }
+
+    #[test]
+    fn rejects_unknown_nested_fields() {
+        assert!(FieldTree::parse("disks.mount_point,network_stats.interface_stats.rx_errors,gpu_info.model").is_ok());
+        assert!(FieldTree::parse("disks.nonexistent").is_err());
+        assert!(FieldTree::parse("cpu_usage.value").is_err());
+        assert!(FieldTree::parse("nonexistent").is_err());
+    }
+}
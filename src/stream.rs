@@ -0,0 +1,82 @@
+use crate::projection::FieldSelector;
+use crate::{AppState, SystemInfo};
+use actix_web::web::Bytes;
+use actix_web::Error as ActixError;
+use futures_util::stream::{self, Stream};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::time::Instant;
+
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+struct EventStream {
+    app: Arc<AppState>,
+    updates: watch::Receiver<u64>,
+    selector: FieldSelector,
+    min_interval: Duration,
+    last_sent: Option<Instant>,
+    // 待发送的快照（续传补发或刚到达的新快照）
+    pending: VecDeque<(u64, Arc<SystemInfo>)>,
+}
+
+impl EventStream {
+    fn event(&self, id: u64, info: &SystemInfo) -> Result<Bytes, ActixError> {
+        let mut value = serde_json::to_value(info)?;
+        if !self.selector.is_identity() {
+            value = self.selector.apply(value);
+        }
+        Ok(Bytes::from(format!("id: {}\nevent: snapshot\ndata: {}\n\n", id, value)))
+    }
+}
+
+// SSE 事件流：每个新快照推送一次，间隔不小于 min_interval；
+// 指定 last_event_id 时先补发之后错过的快照
+pub fn snapshot_events(
+    app: Arc<AppState>,
+    selector: FieldSelector,
+    min_interval: Duration,
+    last_event_id: Option<u64>,
+) -> impl Stream<Item = Result<Bytes, ActixError>> {
+    let mut updates = app.updates.subscribe();
+    updates.mark_unchanged();
+
+    let pending = match last_event_id {
+        Some(id) => app.snapshots_since(id),
+        None => app.latest_snapshot_entry().into_iter().collect(),
+    };
+
+    let state = EventStream {
+        app,
+        updates,
+        selector,
+        min_interval,
+        last_sent: None,
+        pending,
+    };
+
+    stream::unfold(state, |mut st| async move {
+        loop {
+            if let Some((id, info)) = st.pending.pop_front() {
+                let event = st.event(id, &info);
+                st.last_sent = Some(Instant::now());
+                return Some((event, st));
+            }
+
+            // 等待新快照，长时间没有数据时发送注释行保持连接
+            match tokio::time::timeout(KEEPALIVE_INTERVAL, st.updates.changed()).await {
+                Err(_) => return Some((Ok(Bytes::from_static(b": keep-alive\n\n")), st)),
+                Ok(Err(_)) => return None,
+                Ok(Ok(())) => {}
+            }
+
+            // 未到最小间隔时先等待，期间到达的快照只推送最新的一个
+            if let Some(last_sent) = st.last_sent {
+                tokio::time::sleep_until(last_sent + st.min_interval).await;
+            }
+            st.updates.borrow_and_update();
+            st.pending.extend(st.app.latest_snapshot_entry());
+        }
+    })
+}
@@ -0,0 +1,695 @@
+use crate::platform;
+use crate::{CoreTimes, DiskInfo, DiskIoStats, InterfaceStats, Listener, MemoryDetails, NetworkInfo, PerformanceMetrics, PressureStall, ProtocolCounters, ResourcePressure, SystemInfo, TaskStates, TcpStates, TempInfo, TempStatus};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+const SCHEMA_VERSION: u32 = 2;
+
+// v2 响应：按子系统嵌套，字段名带单位，未采集或无意义的值输出 null
+#[derive(Serialize, JsonSchema, Default)]
+pub struct SystemInfoV2 {
+    schema_version: u32,
+    timestamp: DateTime<Utc>,
+    host: HostV2,
+    cpu: CpuV2,
+    memory: MemoryV2,
+    gpu: Option<GpuV2>,
+    storage: StorageV2,
+    network: NetworkV2,
+    processes: ProcessesV2,
+    sensors: SensorsV2,
+    power: Option<PowerV2>,
+    // 内核不支持 PSI 时为 null
+    pressure: Option<PressureV2>,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct HostV2 {
+    system_name: Option<String>,
+    kernel_version: Option<String>,
+    os_version: Option<String>,
+    host_name: Option<String>,
+    boot_time_unix_s: Option<u64>,
+    uptime_s: Option<u64>,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct CpuV2 {
+    brand: String,
+    vendor_id: String,
+    logical_cores: usize,
+    physical_cores: Option<usize>,
+    usage_percent: Vec<f32>,
+    frequency_mhz: Vec<u64>,
+    temperature_celsius: Option<f32>,
+    load_average: Option<LoadAverageV2>,
+    time: Option<CpuTimeV2>,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct LoadAverageV2 {
+    one_min: f64,
+    five_min: f64,
+    fifteen_min: f64,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct CpuTimeV2 {
+    user_percent: f32,
+    nice_percent: f32,
+    system_percent: f32,
+    iowait_percent: f32,
+    irq_percent: f32,
+    softirq_percent: f32,
+    steal_percent: f32,
+    guest_percent: f32,
+    idle_percent: f32,
+    run_queue_length: u64,
+    context_switches: u64,
+    interrupts: u64,
+    context_switches_per_s: Option<f64>,
+    interrupts_per_s: Option<f64>,
+    cores: Vec<CoreTimeV2>,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct CoreTimeV2 {
+    cpu: usize,
+    user_percent: Option<f32>,
+    nice_percent: Option<f32>,
+    system_percent: Option<f32>,
+    idle_percent: Option<f32>,
+    iowait_percent: Option<f32>,
+    irq_percent: Option<f32>,
+    softirq_percent: Option<f32>,
+    steal_percent: Option<f32>,
+    guest_percent: Option<f32>,
+    context_switches_per_s: Option<f64>,
+    interrupts_per_s: Option<f64>,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct MemoryV2 {
+    total_bytes: u64,
+    used_bytes: u64,
+    free_bytes: u64,
+    available_bytes: u64,
+    usage_percent: Option<f32>,
+    swap: SwapV2,
+    details: Option<MemoryDetailsV2>,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct MemoryDetailsV2 {
+    buffers_bytes: u64,
+    cached_bytes: u64,
+    shmem_bytes: u64,
+    slab_bytes: u64,
+    slab_reclaimable_bytes: u64,
+    slab_unreclaimable_bytes: u64,
+    dirty_bytes: u64,
+    writeback_bytes: u64,
+    mapped_bytes: u64,
+    anon_bytes: u64,
+    committed_as_bytes: u64,
+    commit_limit_bytes: u64,
+    commit_percent: Option<f32>,
+    hugepages_total: u64,
+    hugepages_free: u64,
+    hugepages_reserved: u64,
+    hugepages_surplus: u64,
+    hugepage_size_bytes: u64,
+    anon_hugepages_bytes: u64,
+    page_faults: u64,
+    major_faults: u64,
+    swap_in_pages: u64,
+    swap_out_pages: u64,
+    oom_kills: Option<u64>,
+    page_faults_per_s: Option<f64>,
+    major_faults_per_s: Option<f64>,
+    swap_in_pages_per_s: Option<f64>,
+    swap_out_pages_per_s: Option<f64>,
+    oom_kills_per_s: Option<f64>,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct SwapV2 {
+    total_bytes: u64,
+    used_bytes: u64,
+    free_bytes: u64,
+    usage_percent: Option<f32>,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct GpuV2 {
+    vendor: String,
+    model: String,
+    usage_percent: f32,
+    memory_total_bytes: u64,
+    memory_used_bytes: u64,
+    temperature_celsius: Option<f32>,
+    power_watts: Option<f32>,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct StorageV2 {
+    total_bytes: u64,
+    used_bytes: u64,
+    free_bytes: u64,
+    filesystems: Vec<FilesystemV2>,
+    // 本平台未采集磁盘 I/O 时为 null
+    devices: Option<Vec<BlockDeviceV2>>,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct FilesystemV2 {
+    device: String,
+    mount_point: String,
+    file_system: String,
+    read_only: Option<bool>,
+    mount_options: Vec<String>,
+    total_bytes: u64,
+    available_bytes: u64,
+    usage_percent: Option<f32>,
+    inodes_total: Option<u64>,
+    inodes_used: Option<u64>,
+    inodes_free: Option<u64>,
+    inode_usage_percent: Option<f32>,
+    block_device: Option<String>,
+    dm_name: Option<String>,
+    dm_type: Option<String>,
+    physical_devices: Vec<String>,
+    io: Option<FilesystemIoV2>,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct FilesystemIoV2 {
+    read_iops: Option<f64>,
+    write_iops: Option<f64>,
+    read_bytes_per_s: Option<f64>,
+    write_bytes_per_s: Option<f64>,
+    await_ms: Option<f64>,
+    util_percent: Option<f64>,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct BlockDeviceV2 {
+    device: String,
+    reads_completed: u64,
+    writes_completed: u64,
+    read_bytes: u64,
+    written_bytes: u64,
+    read_time_ms: u64,
+    write_time_ms: u64,
+    io_time_ms: u64,
+    partition: bool,
+    dm_name: Option<String>,
+    dm_type: Option<String>,
+    mount_points: Vec<String>,
+    in_flight: u64,
+    weighted_io_time_ms: u64,
+    discards_completed: Option<u64>,
+    discarded_bytes: Option<u64>,
+    discard_time_ms: Option<u64>,
+    flushes_completed: Option<u64>,
+    flush_time_ms: Option<u64>,
+    read_iops: Option<f64>,
+    write_iops: Option<f64>,
+    discard_iops: Option<f64>,
+    flush_iops: Option<f64>,
+    read_bytes_per_s: Option<f64>,
+    write_bytes_per_s: Option<f64>,
+    read_await_ms: Option<f64>,
+    write_await_ms: Option<f64>,
+    await_ms: Option<f64>,
+    util_percent: Option<f64>,
+    queue_depth: Option<f64>,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct NetworkV2 {
+    received_bytes: u64,
+    transmitted_bytes: u64,
+    tcp_sockets: Option<usize>,
+    udp_sockets: Option<usize>,
+    listeners: Option<Vec<Listener>>,
+    tcp_states: Option<TcpStates>,
+    protocol: Option<ProtocolV2>,
+    interfaces: Vec<InterfaceV2>,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct ProtocolV2 {
+    tcp_out_segs: u64,
+    tcp_retrans_segs: u64,
+    tcp_out_rsts: u64,
+    tcp_estab_resets: u64,
+    tcp_attempt_fails: u64,
+    tcp_listen_overflows: u64,
+    tcp_listen_drops: u64,
+    udp_in_errors: u64,
+    udp_rcvbuf_errors: u64,
+    udp_no_ports: u64,
+    tcp_retrans_segs_per_s: Option<f64>,
+    tcp_out_rsts_per_s: Option<f64>,
+    tcp_estab_resets_per_s: Option<f64>,
+    tcp_attempt_fails_per_s: Option<f64>,
+    tcp_listen_overflows_per_s: Option<f64>,
+    tcp_listen_drops_per_s: Option<f64>,
+    udp_in_errors_per_s: Option<f64>,
+    udp_rcvbuf_errors_per_s: Option<f64>,
+    udp_no_ports_per_s: Option<f64>,
+    tcp_retrans_percent: Option<f64>,
+    sockets_used: u64,
+    tcp_inuse: u64,
+    tcp_orphan: u64,
+    tcp_time_wait: u64,
+    tcp_memory_bytes: u64,
+    udp_inuse: u64,
+    udp_memory_bytes: u64,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct InterfaceV2 {
+    name: String,
+    mac_address: Option<String>,
+    ip_addresses: Vec<String>,
+    received_bytes: u64,
+    transmitted_bytes: u64,
+    received_packets: u64,
+    transmitted_packets: u64,
+    rx_errors: Option<u64>,
+    tx_errors: Option<u64>,
+    rx_dropped: Option<u64>,
+    tx_dropped: Option<u64>,
+    rx_bytes_per_s: Option<f64>,
+    tx_bytes_per_s: Option<f64>,
+    rx_packets_per_s: Option<f64>,
+    tx_packets_per_s: Option<f64>,
+    link: Option<LinkV2>,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct LinkV2 {
+    operstate: String,
+    carrier: Option<bool>,
+    mtu_bytes: Option<u32>,
+    speed_mbps: Option<u32>,
+    duplex: Option<String>,
+    driver: Option<String>,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct ProcessesV2 {
+    total: usize,
+    threads: Option<usize>,
+    running: usize,
+    sleeping: usize,
+    zombie: usize,
+    blocked: usize,
+    // 仅 Linux 提供
+    states: Option<TaskStates>,
+    runnable_tasks: Option<u64>,
+    blocked_tasks: Option<u64>,
+    cpu_usage_percent_sum: f32,
+    memory_bytes_sum: u64,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct SensorsV2 {
+    temperatures: Vec<TemperatureV2>,
+    warnings: Vec<String>,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct TemperatureV2 {
+    label: String,
+    celsius: f32,
+    status: TempStatus,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct PowerV2 {
+    ac_powered: bool,
+    battery_present: bool,
+    battery_percent: Option<f32>,
+    battery_time_remaining_s: Option<u64>,
+    consumption_watts: Option<f32>,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct PressureV2 {
+    cpu: Option<ResourcePressureV2>,
+    memory: Option<ResourcePressureV2>,
+    io: Option<ResourcePressureV2>,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct ResourcePressureV2 {
+    some: PressureStallV2,
+    full: Option<PressureStallV2>,
+}
+
+#[derive(Serialize, JsonSchema, Default)]
+pub struct PressureStallV2 {
+    avg10_percent: f64,
+    avg60_percent: f64,
+    avg300_percent: f64,
+    total_us: u64,
+}
+
+// 比例在分母为 0 时没有意义
+fn percent(part: u64, total: u64) -> Option<f32> {
+    (total > 0).then(|| part as f32 / total as f32 * 100.0)
+}
+
+impl SystemInfoV2 {
+    pub fn from_snapshot(info: &SystemInfo) -> Self {
+        let caps = &platform::CAPABILITIES;
+        let perf = &info.performance_metrics;
+        let stats = &info.network_stats;
+
+        SystemInfoV2 {
+            schema_version: SCHEMA_VERSION,
+            timestamp: info.timestamp,
+            host: HostV2 {
+                system_name: info.system_name.clone(),
+                kernel_version: info.kernel_version.clone(),
+                os_version: info.os_version.clone(),
+                host_name: info.host_name.clone(),
+                boot_time_unix_s: (info.boot_time > 0).then_some(info.boot_time),
+                uptime_s: (info.boot_time > 0).then_some(info.uptime),
+            },
+            cpu: CpuV2 {
+                brand: info.cpu_brand.clone(),
+                vendor_id: info.cpu_vendor_id.clone(),
+                logical_cores: info.cpu_cores,
+                physical_cores: (info.cpu_physical_cores > 0).then_some(info.cpu_physical_cores),
+                usage_percent: info.cpu_usage.clone(),
+                frequency_mhz: info.cpu_frequency.clone(),
+                temperature_celsius: info.cpu_temp,
+                load_average: caps.load_average.then_some(LoadAverageV2 {
+                    one_min: info.load_average.one,
+                    five_min: info.load_average.five,
+                    fifteen_min: info.load_average.fifteen,
+                }),
+                time: caps.cpu_time.then(|| cpu_time(perf)).flatten(),
+            },
+            memory: MemoryV2 {
+                total_bytes: info.memory_total,
+                used_bytes: info.memory_used,
+                free_bytes: info.memory_free,
+                available_bytes: info.memory_available,
+                usage_percent: percent(info.memory_used, info.memory_total),
+                swap: SwapV2 {
+                    total_bytes: info.swap_total,
+                    used_bytes: info.swap_used,
+                    free_bytes: info.swap_free,
+                    usage_percent: percent(info.swap_used, info.swap_total),
+                },
+                details: info.memory_details.as_ref().map(memory_details),
+            },
+            gpu: info.gpu_info.as_ref().map(|gpu| GpuV2 {
+                vendor: gpu.vendor.clone(),
+                model: gpu.model.clone(),
+                usage_percent: gpu.usage,
+                memory_total_bytes: gpu.memory_total,
+                memory_used_bytes: gpu.memory_used,
+                temperature_celsius: gpu.temperature,
+                power_watts: gpu.power_usage,
+            }),
+            storage: StorageV2 {
+                total_bytes: info.total_disk_space,
+                used_bytes: info.total_disk_used,
+                free_bytes: info.total_disk_free,
+                filesystems: info.disks.iter().map(filesystem).collect(),
+                devices: caps.disk_io.then(|| info.disk_io_stats.iter().map(block_device).collect()),
+            },
+            network: NetworkV2 {
+                received_bytes: info.total_rx_bytes,
+                transmitted_bytes: info.total_tx_bytes,
+                tcp_sockets: caps.connections.then_some(stats.tcp_connections),
+                udp_sockets: caps.connections.then_some(stats.udp_connections),
+                listeners: caps.connections.then(|| stats.listeners.clone()),
+                tcp_states: stats.tcp_states.clone(),
+                protocol: stats.protocol_counters.as_ref().map(protocol),
+                interfaces: info.networks.iter().map(|network| {
+                    let counters = stats.interface_stats.iter()
+                        .find(|s| s.name == network.interface)
+                        .filter(|_| caps.interface_counters);
+                    interface(network, counters)
+                }).collect(),
+            },
+            processes: ProcessesV2 {
+                total: info.process_count,
+                threads: caps.thread_count.then_some(info.thread_count),
+                running: info.running_process_count,
+                sleeping: info.process_stats.sleeping_count,
+                zombie: info.process_stats.zombie_count,
+                blocked: info.process_stats.blocked_count,
+                states: info.process_stats.task_states.clone(),
+                runnable_tasks: info.process_stats.procs_running,
+                blocked_tasks: info.process_stats.procs_blocked,
+                cpu_usage_percent_sum: info.process_stats.total_cpu_usage,
+                memory_bytes_sum: info.process_stats.total_memory_usage,
+            },
+            sensors: SensorsV2 {
+                temperatures: info.temperatures.iter().map(temperature).collect(),
+                warnings: info.temp_warnings.clone(),
+            },
+            power: caps.power.then_some(PowerV2 {
+                ac_powered: info.power_info.ac_powered,
+                battery_present: info.power_info.battery_present,
+                battery_percent: info.power_info.battery_percentage,
+                battery_time_remaining_s: info.power_info.battery_time_remaining,
+                consumption_watts: info.power_info.power_consumption,
+            }),
+            pressure: perf.pressure.available.then(|| PressureV2 {
+                cpu: perf.pressure.cpu.as_ref().map(resource_pressure),
+                memory: perf.pressure.memory.as_ref().map(resource_pressure),
+                io: perf.pressure.io.as_ref().map(resource_pressure),
+            }),
+        }
+    }
+}
+
+fn pressure_stall(stall: &PressureStall) -> PressureStallV2 {
+    PressureStallV2 {
+        avg10_percent: stall.avg10,
+        avg60_percent: stall.avg60,
+        avg300_percent: stall.avg300,
+        total_us: stall.total,
+    }
+}
+
+fn resource_pressure(pressure: &ResourcePressure) -> ResourcePressureV2 {
+    ResourcePressureV2 {
+        some: pressure_stall(&pressure.some),
+        full: pressure.full.as_ref().map(pressure_stall),
+    }
+}
+
+fn filesystem(disk: &DiskInfo) -> FilesystemV2 {
+    FilesystemV2 {
+        device: disk.name.clone(),
+        mount_point: disk.mount_point.clone(),
+        file_system: disk.file_system.clone(),
+        read_only: disk.read_only,
+        mount_options: disk.mount_options.clone(),
+        total_bytes: disk.total_space,
+        available_bytes: disk.available_space,
+        usage_percent: percent(disk.total_space.saturating_sub(disk.available_space), disk.total_space),
+        inodes_total: disk.inodes_total,
+        inodes_used: disk.inodes_used,
+        inodes_free: disk.inodes_free,
+        inode_usage_percent: disk.inodes_total.zip(disk.inodes_used).and_then(|(total, used)| percent(used, total)),
+        block_device: disk.block_device.clone(),
+        dm_name: disk.dm_name.clone(),
+        dm_type: disk.dm_type.clone(),
+        physical_devices: disk.physical_devices.clone(),
+        io: disk.io.as_ref().map(|io| FilesystemIoV2 {
+            read_iops: io.read_iops,
+            write_iops: io.write_iops,
+            read_bytes_per_s: io.read_bytes_sec,
+            write_bytes_per_s: io.write_bytes_sec,
+            await_ms: io.io_await,
+            util_percent: io.util_percentage,
+        }),
+    }
+}
+
+fn block_device(io: &DiskIoStats) -> BlockDeviceV2 {
+    BlockDeviceV2 {
+        device: io.device.clone(),
+        reads_completed: io.reads,
+        writes_completed: io.writes,
+        read_bytes: io.read_bytes,
+        written_bytes: io.write_bytes,
+        read_time_ms: io.read_time,
+        write_time_ms: io.write_time,
+        io_time_ms: io.io_time,
+        partition: io.partition,
+        dm_name: io.dm_name.clone(),
+        dm_type: io.dm_type.clone(),
+        mount_points: io.mount_points.clone(),
+        in_flight: io.io_in_progress,
+        weighted_io_time_ms: io.weighted_io_time,
+        discards_completed: io.discards,
+        discarded_bytes: io.discard_bytes,
+        discard_time_ms: io.discard_time,
+        flushes_completed: io.flushes,
+        flush_time_ms: io.flush_time,
+        read_iops: io.read_iops,
+        write_iops: io.write_iops,
+        discard_iops: io.discard_iops,
+        flush_iops: io.flush_iops,
+        read_bytes_per_s: io.read_bytes_sec,
+        write_bytes_per_s: io.write_bytes_sec,
+        read_await_ms: io.read_await,
+        write_await_ms: io.write_await,
+        await_ms: io.io_await,
+        util_percent: io.util_percentage,
+        queue_depth: io.queue_depth,
+    }
+}
+
+fn memory_details(details: &MemoryDetails) -> MemoryDetailsV2 {
+    MemoryDetailsV2 {
+        buffers_bytes: details.buffers,
+        cached_bytes: details.cached,
+        shmem_bytes: details.shmem,
+        slab_bytes: details.slab,
+        slab_reclaimable_bytes: details.slab_reclaimable,
+        slab_unreclaimable_bytes: details.slab_unreclaimable,
+        dirty_bytes: details.dirty,
+        writeback_bytes: details.writeback,
+        mapped_bytes: details.mapped,
+        anon_bytes: details.anon,
+        committed_as_bytes: details.committed_as,
+        commit_limit_bytes: details.commit_limit,
+        commit_percent: percent(details.committed_as, details.commit_limit),
+        hugepages_total: details.hugepages_total,
+        hugepages_free: details.hugepages_free,
+        hugepages_reserved: details.hugepages_reserved,
+        hugepages_surplus: details.hugepages_surplus,
+        hugepage_size_bytes: details.hugepage_size,
+        anon_hugepages_bytes: details.anon_hugepages,
+        page_faults: details.page_faults,
+        major_faults: details.major_faults,
+        swap_in_pages: details.swap_in,
+        swap_out_pages: details.swap_out,
+        oom_kills: details.oom_kills,
+        page_faults_per_s: details.page_faults_sec,
+        major_faults_per_s: details.major_faults_sec,
+        swap_in_pages_per_s: details.swap_in_sec,
+        swap_out_pages_per_s: details.swap_out_sec,
+        oom_kills_per_s: details.oom_kills_sec,
+    }
+}
+
+fn protocol(counters: &ProtocolCounters) -> ProtocolV2 {
+    ProtocolV2 {
+        tcp_out_segs: counters.tcp_out_segs,
+        tcp_retrans_segs: counters.tcp_retrans_segs,
+        tcp_out_rsts: counters.tcp_out_rsts,
+        tcp_estab_resets: counters.tcp_estab_resets,
+        tcp_attempt_fails: counters.tcp_attempt_fails,
+        tcp_listen_overflows: counters.tcp_listen_overflows,
+        tcp_listen_drops: counters.tcp_listen_drops,
+        udp_in_errors: counters.udp_in_errors,
+        udp_rcvbuf_errors: counters.udp_rcvbuf_errors,
+        udp_no_ports: counters.udp_no_ports,
+        tcp_retrans_segs_per_s: counters.tcp_retrans_segs_sec,
+        tcp_out_rsts_per_s: counters.tcp_out_rsts_sec,
+        tcp_estab_resets_per_s: counters.tcp_estab_resets_sec,
+        tcp_attempt_fails_per_s: counters.tcp_attempt_fails_sec,
+        tcp_listen_overflows_per_s: counters.tcp_listen_overflows_sec,
+        tcp_listen_drops_per_s: counters.tcp_listen_drops_sec,
+        udp_in_errors_per_s: counters.udp_in_errors_sec,
+        udp_rcvbuf_errors_per_s: counters.udp_rcvbuf_errors_sec,
+        udp_no_ports_per_s: counters.udp_no_ports_sec,
+        tcp_retrans_percent: counters.tcp_retrans_percentage,
+        sockets_used: counters.sockets_used,
+        tcp_inuse: counters.tcp_inuse,
+        tcp_orphan: counters.tcp_orphan,
+        tcp_time_wait: counters.tcp_time_wait,
+        tcp_memory_bytes: counters.tcp_memory,
+        udp_inuse: counters.udp_inuse,
+        udp_memory_bytes: counters.udp_memory,
+    }
+}
+
+fn interface(network: &NetworkInfo, counters: Option<&InterfaceStats>) -> InterfaceV2 {
+    InterfaceV2 {
+        name: network.interface.clone(),
+        mac_address: network.mac_address.clone(),
+        ip_addresses: network.ip_addresses.clone(),
+        received_bytes: network.received_bytes,
+        transmitted_bytes: network.transmitted_bytes,
+        received_packets: network.received_packets,
+        transmitted_packets: network.transmitted_packets,
+        rx_errors: counters.map(|c| c.rx_errors),
+        tx_errors: counters.map(|c| c.tx_errors),
+        rx_dropped: counters.map(|c| c.rx_dropped),
+        tx_dropped: counters.map(|c| c.tx_dropped),
+        rx_bytes_per_s: counters.and_then(|c| c.rx_bytes_sec),
+        tx_bytes_per_s: counters.and_then(|c| c.tx_bytes_sec),
+        rx_packets_per_s: counters.and_then(|c| c.rx_packets_sec),
+        tx_packets_per_s: counters.and_then(|c| c.tx_packets_sec),
+        link: network.link.as_ref().map(|link| LinkV2 {
+            operstate: link.operstate.clone(),
+            carrier: link.carrier,
+            mtu_bytes: link.mtu,
+            speed_mbps: link.speed,
+            duplex: link.duplex.clone(),
+            driver: link.driver.clone(),
+        }),
+    }
+}
+
+// 首次采样还没有基线时整体为 null
+fn cpu_time(perf: &PerformanceMetrics) -> Option<CpuTimeV2> {
+    Some(CpuTimeV2 {
+        user_percent: perf.user_percentage?,
+        nice_percent: perf.nice_percentage?,
+        system_percent: perf.system_percentage?,
+        iowait_percent: perf.iowait_percentage?,
+        irq_percent: perf.irq_percentage?,
+        softirq_percent: perf.softirq_percentage?,
+        steal_percent: perf.steal_percentage?,
+        guest_percent: perf.guest_percentage?,
+        idle_percent: perf.idle_percentage?,
+        run_queue_length: perf.cpu_queue_length,
+        context_switches: perf.context_switches,
+        interrupts: perf.interrupts,
+        context_switches_per_s: perf.context_switches_per_sec,
+        interrupts_per_s: perf.interrupts_per_sec,
+        cores: perf.per_core.iter().map(core_time).collect(),
+    })
+}
+
+fn core_time(core: &CoreTimes) -> CoreTimeV2 {
+    CoreTimeV2 {
+        cpu: core.cpu,
+        user_percent: core.user_percentage,
+        nice_percent: core.nice_percentage,
+        system_percent: core.system_percentage,
+        idle_percent: core.idle_percentage,
+        iowait_percent: core.iowait_percentage,
+        irq_percent: core.irq_percentage,
+        softirq_percent: core.softirq_percentage,
+        steal_percent: core.steal_percentage,
+        guest_percent: core.guest_percentage,
+        context_switches_per_s: core.context_switches_per_sec,
+        interrupts_per_s: core.interrupts_per_sec,
+    }
+}
+
+fn temperature(temp: &TempInfo) -> TemperatureV2 {
+    TemperatureV2 {
+        label: temp.label.clone(),
+        celsius: temp.temp,
+        status: temp.status.clone(),
+    }
+}
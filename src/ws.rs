@@ -0,0 +1,226 @@
+use crate::{AppState, SystemInfo, TempStatus};
+use actix_ws::{Message, MessageStream, Session};
+use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+use log::debug;
+
+const MIN_TOPIC_INTERVAL_MS: u64 = 100;
+
+// 可订阅的主题，载荷与对应的子系统接口一致
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+enum Topic {
+    System,
+    Cpu,
+    Memory,
+    Disks,
+    Network,
+    Sensors,
+    Power,
+    Processes,
+    Performance,
+    Gpu,
+    Alerts,
+}
+
+impl Topic {
+    fn payload(self, info: &SystemInfo) -> serde_json::Result<Value> {
+        match self {
+            Topic::System => serde_json::to_value(info),
+            Topic::Cpu => serde_json::to_value(info.cpu()),
+            Topic::Memory => serde_json::to_value(info.memory()),
+            Topic::Disks => serde_json::to_value(info.disks()),
+            Topic::Network => serde_json::to_value(info.network()),
+            Topic::Sensors => serde_json::to_value(info.sensors()),
+            Topic::Power => serde_json::to_value(&info.power_info),
+            Topic::Processes => serde_json::to_value(info.process_summary()),
+            Topic::Performance => serde_json::to_value(&info.performance_metrics),
+            Topic::Gpu => serde_json::to_value(&info.gpu_info),
+            Topic::Alerts => serde_json::to_value(alerts(info)),
+        }
+    }
+
+    // 变化订阅比较的内容：告警只比较传感器和级别，告警期间读数的波动不触发推送
+    fn change_key(self, payload: &Value) -> Value {
+        match self {
+            Topic::Alerts => payload.as_array()
+                .map(|alerts| alerts.iter().map(|alert| json!([alert["label"], alert["status"]])).collect())
+                .unwrap_or_default(),
+            _ => payload.clone(),
+        }
+    }
+}
+
+// 处于告警状态的传感器
+#[derive(Serialize)]
+struct Alert<'a> {
+    label: &'a str,
+    status: &'a TempStatus,
+    temp: f32,
+}
+
+fn alerts(info: &SystemInfo) -> Vec<Alert<'_>> {
+    info.temperatures.iter()
+        .filter(|t| !matches!(t.status, TempStatus::Normal))
+        .map(|t| Alert { label: &t.label, status: &t.status, temp: t.temp })
+        .collect()
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum ClientMessage {
+    // 不带 interval_ms 时在数据变化时推送
+    Subscribe { topic: Topic, interval_ms: Option<u64> },
+    Unsubscribe { topic: Topic },
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage<'a> {
+    Subscribed { topic: Topic, interval_ms: Option<u64> },
+    Unsubscribed { topic: Topic },
+    Data { topic: Topic, timestamp: DateTime<Utc>, data: &'a Value },
+    Error { message: String },
+}
+
+struct Subscription {
+    // None 表示数据变化时推送
+    interval: Option<Duration>,
+    next_due: Instant,
+    // 上次推送内容的比较依据，见 Topic::change_key
+    last_key: Option<Value>,
+}
+
+// 单个 WebSocket 连接的处理循环
+pub async fn run(app: Arc<AppState>, mut session: Session, mut messages: MessageStream) {
+    let mut updates = app.updates.subscribe();
+    let mut subscriptions: HashMap<Topic, Subscription> = HashMap::new();
+
+    loop {
+        let next_due = subscriptions.values()
+            .filter(|s| s.interval.is_some())
+            .map(|s| s.next_due)
+            .min();
+        let on_change = subscriptions.values().any(|s| s.interval.is_none());
+
+        let result = tokio::select! {
+            message = messages.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    handle_text(&app, &text, &mut subscriptions, &mut session).await
+                }
+                Some(Ok(Message::Ping(bytes))) => session.pong(&bytes).await.is_ok(),
+                Some(Ok(Message::Close(reason))) => {
+                    let _ = session.close(reason).await;
+                    return;
+                }
+                Some(Ok(_)) => true,
+                Some(Err(e)) => {
+                    debug!("WebSocket protocol error: {}", e);
+                    false
+                }
+                None => false,
+            },
+            _ = tokio::time::sleep_until(next_due.unwrap_or_else(Instant::now)), if next_due.is_some() => {
+                push_due(&app, &mut subscriptions, &mut session).await
+            }
+            changed = updates.changed(), if on_change => {
+                changed.is_ok() && push_changes(&app, &mut subscriptions, &mut session).await
+            }
+        };
+
+        if !result {
+            return;
+        }
+    }
+}
+
+async fn handle_text(
+    app: &Arc<AppState>,
+    text: &str,
+    subscriptions: &mut HashMap<Topic, Subscription>,
+    session: &mut Session,
+) -> bool {
+    let message = match serde_json::from_str::<ClientMessage>(text) {
+        Ok(message) => message,
+        Err(e) => {
+            return send(session, &ServerMessage::Error { message: format!("Invalid message: {}", e) }).await;
+        }
+    };
+
+    match message {
+        ClientMessage::Subscribe { topic, interval_ms } => {
+            let interval_ms = interval_ms.map(|ms| ms.max(MIN_TOPIC_INTERVAL_MS));
+            subscriptions.insert(topic, Subscription {
+                interval: interval_ms.map(Duration::from_millis),
+                next_due: Instant::now(),
+                last_key: None,
+            });
+            send(session, &ServerMessage::Subscribed { topic, interval_ms }).await
+                && push_changes(app, subscriptions, session).await
+        }
+        ClientMessage::Unsubscribe { topic } => {
+            subscriptions.remove(&topic);
+            send(session, &ServerMessage::Unsubscribed { topic }).await
+        }
+    }
+}
+
+// 推送到期的定时主题：内容取自最新发布的快照，不单独刷新，避免打乱后台采样的 CPU 使用率窗口
+async fn push_due(
+    app: &Arc<AppState>,
+    subscriptions: &mut HashMap<Topic, Subscription>,
+    session: &mut Session,
+) -> bool {
+    let now = Instant::now();
+    let Some(info) = app.snapshot.load_full() else { return true };
+    for (topic, subscription) in subscriptions.iter_mut() {
+        let Some(interval) = subscription.interval else { continue };
+        if subscription.next_due > now {
+            continue;
+        }
+        subscription.next_due = now + interval;
+
+        let Ok(data) = topic.payload(&info) else { continue };
+        if !send(session, &ServerMessage::Data { topic: *topic, timestamp: info.timestamp, data: &data }).await {
+            return false;
+        }
+    }
+    true
+}
+
+// 推送变化订阅：与上次推送的内容不同才发送
+async fn push_changes(
+    app: &Arc<AppState>,
+    subscriptions: &mut HashMap<Topic, Subscription>,
+    session: &mut Session,
+) -> bool {
+    let Some(info) = app.snapshot.load_full() else { return true };
+    for (topic, subscription) in subscriptions.iter_mut() {
+        if subscription.interval.is_some() {
+            continue;
+        }
+        let Ok(data) = topic.payload(&info) else { continue };
+        let key = topic.change_key(&data);
+        if subscription.last_key.as_ref() == Some(&key) {
+            continue;
+        }
+        if !send(session, &ServerMessage::Data { topic: *topic, timestamp: info.timestamp, data: &data }).await {
+            return false;
+        }
+        subscription.last_key = Some(key);
+    }
+    true
+}
+
+async fn send(session: &mut Session, message: &ServerMessage<'_>) -> bool {
+    match serde_json::to_string(message) {
+        Ok(text) => session.text(text).await.is_ok(),
+        Err(_) => true,
+    }
+}